@@ -0,0 +1,74 @@
+use rs_pdf::block_document::block_container::BlockContainer;
+use rs_pdf::block_document::direction::Direction;
+use rs_pdf::block_document::document::Document;
+use rs_pdf::block_document::flexible_container::FlexibleContainer;
+use rs_pdf::block_document::geometry::GeoRect;
+use rs_pdf::block_document::image::Image;
+use rs_pdf::block_document::line::Line;
+use rs_pdf::block_document::page_number::PageNumber;
+use rs_pdf::block_document::page_size::A4;
+use rs_pdf::block_document::pdf_writer;
+use rs_pdf::block_document::rectangle::Rectangle;
+use rs_pdf::block_document::style::{Alignment, HorizontalAlignment, RgbColor, Space};
+use rs_pdf::block_document::text::Text;
+use rs_pdf::block_document::wrapper::Wrapper;
+use std::env;
+use std::fs::File;
+
+// NOTE: JSON を使わずに Document を組み立てて PDF を出力する例
+// NOTE: cargo run --example builder -- output.pdf
+fn main() {
+    let output = env::args().nth(1).unwrap_or("builder.pdf".to_string());
+    let assets = concat!(env!("CARGO_MANIFEST_DIR"), "/assets");
+    let gray = RgbColor { r: 100, g: 100, b: 100 };
+
+    let document = Document::builder("rs-pdf", format!("{}/fonts/NotoSansCJKjp-Thin.ttf", assets))
+        .page_size(A4)
+        .margin(Space { top: 15.0, right: 15.0, bottom: 15.0, left: 15.0 })
+        .page_number(PageNumber::builder(10.0).text_fill_color(gray.clone()))
+        .dynamic_page(|page| {
+            let page = page
+                .common(
+                    BlockContainer::builder()
+                        .point(15.0, 15.0)
+                        .direction(Direction::Vertical)
+                        .object(Image::builder(format!("{}/images/delivery_note_rgb.png", assets)))
+                        .object(
+                            Wrapper::builder(Text::builder("〒000-0000", 10.0))
+                                .space(Space { top: 5.0, right: 0.0, bottom: 0.0, left: 0.0 }),
+                        ),
+                )
+                .content_frame(GeoRect::new(180.0, 220.0, 15.0, 60.0));
+
+            // NOTE: 明細のように Rust 側のデータから行を作る
+            (1..=40).fold(page, |page, i| {
+                page.content(
+                    FlexibleContainer::builder()
+                        .size(180.0, 8.0)
+                        .direction(Direction::Horizontal)
+                        .item(Text::builder(format!("商品 {}", i), 10.0), Some(70.0))
+                        .item(
+                            Text::builder(format!("{} 円", i * 1000), 10.0).alignment(Alignment {
+                                horizontal: Some(HorizontalAlignment::Right),
+                                vertical: None,
+                            }),
+                            Some(30.0),
+                        ),
+                )
+                .content(Line::builder(0.0, 0.0, 180.0, 0.0).border_color(gray.clone()))
+            })
+        })
+        .static_page(|page| {
+            page.object(
+                Rectangle::builder()
+                    .frame(GeoRect::new(180.0, 20.0, 15.0, 15.0))
+                    .background_color(RgbColor { r: 240, g: 240, b: 240 }),
+            )
+            .object(Text::builder("以上", 12.0).point(20.0, 20.0))
+        })
+        .build();
+
+    let file = File::create(&output).unwrap_or_else(|e| panic!("Could not create {}: {}", output, e));
+    pdf_writer::save_laid_out(vec![pdf_writer::layout(&document, false)], file, false, false)
+        .unwrap_or_else(|e| panic!("Could not write {}: {}", output, e));
+}
@@ -1,20 +1,40 @@
+pub mod asset_path;
+pub mod batch;
 pub mod block;
+pub mod builder;
 pub mod block_container;
+pub mod data_uri;
+pub mod debug_overlay;
+pub mod diagnostic;
 pub mod static_page;
 pub mod direction;
 pub mod document;
 pub mod document_json;
+pub mod document_schema;
+pub mod document_serde;
 pub mod flexible_container;
 pub mod flexible_item;
 pub mod geometry;
 pub mod image;
+pub mod input_format;
+pub mod layout_dump;
 pub mod line;
+pub mod output_format;
 pub mod pdf_writer;
+pub mod png_writer;
 pub mod rectangle;
+pub mod reproducible;
+pub mod resource;
+pub mod server;
 pub mod style;
+pub mod template;
 pub mod text;
 pub mod text_renderer;
+pub mod text_variable;
+pub mod validation;
+pub mod watch;
 pub mod wrapper;
 pub mod page;
+pub mod page_size;
 pub mod dynamic_page;
-mod page_number;
+pub mod page_number;
@@ -0,0 +1,74 @@
+use crate::block_document::data_uri;
+use std::env;
+use std::path::{Path, PathBuf};
+
+// NOTE: フォントや画像などの相対パスを解決する
+// NOTE: 入力ファイルのディレクトリ → asset_roots（指定順）→ カレントディレクトリの順に探す
+#[derive(Debug, Clone, Default)]
+pub struct AssetPaths {
+    pub base_dir: PathBuf, // NOTE: 空の場合はカレントディレクトリ
+    pub asset_roots: Vec<PathBuf>,
+}
+
+impl AssetPaths {
+    pub fn new(base_dir: PathBuf) -> AssetPaths {
+        AssetPaths {
+            base_dir: absolute(&base_dir),
+            asset_roots: Vec::new(),
+        }
+    }
+
+    pub fn add_asset_root(&mut self, asset_root: PathBuf) {
+        self.asset_roots.push(absolute(&asset_root));
+    }
+
+    // NOTE: 見つからない場合は入力ファイルのディレクトリを基準にした絶対パスを返す（エラーメッセージで解決後のパスを表示するため）
+    pub fn resolve(&self, path: &str) -> String {
+        if data_uri::is_data_uri(path) || Path::new(path).is_absolute() {
+            return path.to_string();
+        }
+
+        let candidates: Vec<PathBuf> = [&self.base_dir]
+            .into_iter()
+            .chain(self.asset_roots.iter())
+            .map(|dir| dir.join(path))
+            .chain([absolute(Path::new(path))])
+            .collect();
+
+        candidates
+            .iter()
+            .find(|candidate| candidate.exists())
+            .unwrap_or(&candidates[0])
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+impl AssetPaths {
+    // NOTE: resolve で解決したパスを探したディレクトリからの相対パスに戻す（環境によらない出力にするため）
+    // NOTE: どのディレクトリにも含まれない場合（絶対パスの指定など）はそのまま返す
+    pub fn relative(&self, path: &str) -> String {
+        if data_uri::is_data_uri(path) {
+            return path.to_string();
+        }
+
+        [&self.base_dir]
+            .into_iter()
+            .chain(self.asset_roots.iter())
+            .find_map(|dir| Path::new(path).strip_prefix(dir).ok())
+            .map(|relative_path| relative_path.to_string_lossy().to_string())
+            .unwrap_or(path.to_string())
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+
+    std::path::absolute(path)
+        .or_else(|_| env::current_dir().map(|dir| dir.join(path)))
+        .unwrap_or(path.to_path_buf())
+}
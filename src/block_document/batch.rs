@@ -0,0 +1,140 @@
+use crate::block_document::template;
+use serde_json::{Map, Value};
+use std::fs::read_to_string;
+use std::path::Path;
+use std::process::exit;
+
+// NOTE: 差し込み印刷用のレコードを読み込む（CSV または JSON Lines）
+// NOTE: 読み込めないレコードはエラーメッセージにする（他のレコードの処理を続けられるように）
+pub fn read_records(path: &str) -> Vec<Result<Value, String>> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+
+    match extension.as_deref() {
+        Some("csv") => read_csv(path),
+        Some("jsonl") | Some("ndjson") => read_json_lines(path),
+        _ => {
+            eprintln!("Unsupported data file: {} (csv, jsonl or ndjson)", path);
+            exit(1);
+        }
+    }
+}
+
+// NOTE: 列名の "." で階層化する（"customer.name" → {"customer": {"name": ...}}）
+// NOTE: 値はすべて文字列（"00123" などの ID を変えないため）。数値や真偽値を差し込む場合は JSON Lines を使用する
+fn read_csv(path: &str) -> Vec<Result<Value, String>> {
+    let mut reader = csv::Reader::from_path(path).unwrap_or_else(|e| {
+        eprintln!("Failed to open data file: {} ({})", path, e);
+        exit(1);
+    });
+
+    let headers = reader.headers().cloned().unwrap_or_else(|e| {
+        eprintln!("Failed to read CSV header: {} ({})", path, e);
+        exit(1);
+    });
+
+    reader
+        .records()
+        .map(|row| {
+            let row = row.map_err(|e| format!("Failed to read CSV record: {} ({})", path, e))?;
+
+            let mut record = Map::new();
+            for (header, field) in headers.iter().zip(row.iter()) {
+                insert_path(&mut record, header, Value::String(field.to_string()));
+            }
+
+            Ok(Value::Object(record))
+        })
+        .collect()
+}
+
+fn read_json_lines(path: &str) -> Vec<Result<Value, String>> {
+    let lines = read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Failed to open data file: {} ({})", path, e);
+        exit(1);
+    });
+
+    lines
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| format!("Invalid JSON at line {}: {} ({})", i + 1, path, e))
+        })
+        .collect()
+}
+
+fn insert_path(record: &mut Map<String, Value>, path: &str, value: Value) {
+    let mut current = record;
+    let mut segments = path.split('.').map(|segment| segment.trim()).peekable();
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_none() {
+            current.insert(segment.to_string(), value);
+
+            return;
+        }
+
+        let child = current
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !child.is_object() {
+            *child = Value::Object(Map::new());
+        }
+        current = child.as_object_mut().unwrap();
+    }
+}
+
+// NOTE: ファイル名のパターンにレコードの値を差し込む（例: note-{{order_id}}.pdf）
+pub fn file_name(pattern: &str, record: &Value) -> Result<String, String> {
+    let file_name = match template::bind(&Value::String(pattern.to_string()), record) {
+        Value::String(file_name) => file_name,
+        value => value.to_string(),
+    };
+
+    // NOTE: 出力ディレクトリの外に書き込まないように区切り文字を置き換える
+    let file_name = file_name.replace(['/', '\\'], "_");
+
+    // NOTE: 空や "." / ".." は出力ディレクトリ自体やその親を指すため使用できない
+    if file_name.trim().is_empty() || file_name.trim().chars().all(|c| c == '.') {
+        return Err(format!("Invalid output file name: \"{}\" (pattern: {})", file_name, pattern));
+    }
+
+    Ok(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn csv_values_are_strings() {
+        let path = std::env::temp_dir().join(format!("rs-pdf-batch-{}.csv", std::process::id()));
+        std::fs::write(&path, "id,size.width,member\n00123,210,true\n").unwrap();
+        let records = read_records(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let record = records[0].clone().unwrap();
+        assert_eq!(record, json!({ "id": "00123", "size": { "width": "210" }, "member": "true" }));
+        // NOTE: 文字列全体のプレースホルダーでも文字列のまま差し込まれる（数値が必要な項目には使用できない）
+        assert_eq!(template::bind(&json!({ "width": "{{size.width}}" }), &record), json!({ "width": "210" }));
+    }
+
+    #[test]
+    fn file_name_replaces_separators() {
+        let record = json!({ "id": "../a/b\\c" });
+
+        assert_eq!(file_name("note-{{id}}.pdf", &record), Ok("note-.._a_b_c.pdf".to_string()));
+    }
+
+    #[test]
+    fn file_name_rejects_dot_and_empty_names() {
+        for id in ["", " ", ".", ".."] {
+            assert!(file_name("{{id}}", &json!({ "id": id })).is_err(), "{:?}", id);
+        }
+        assert!(file_name("{{missing}}", &json!({})).is_err());
+        assert_eq!(file_name("{{id}}", &json!({ "id": ".hidden" })), Ok(".hidden".to_string()));
+    }
+}
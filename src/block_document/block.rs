@@ -1,13 +1,19 @@
 use crate::block_document::block_container::BlockContainer;
 use crate::block_document::flexible_container::FlexibleContainer;
 use crate::block_document::flexible_item::FlexibleItem;
+use crate::block_document::geometry::GeoRect;
 use crate::block_document::image::Image;
 use crate::block_document::line::Line;
 use crate::block_document::rectangle::Rectangle;
 use crate::block_document::text::Text;
 use crate::block_document::wrapper::Wrapper;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "crate::block_document::document_serde::BlockJson")]
+#[serde(into = "crate::block_document::document_serde::BlockJson")]
+#[schemars(rename = "any_object")]
 pub enum Block {
     Container(BlockContainer),
     Flexible(FlexibleContainer),
@@ -18,3 +24,89 @@ pub enum Block {
     Image(Image),
     Wrapper(Box<Wrapper>),
 }
+
+impl Block {
+    // NOTE: 子孫を含むすべてのテキスト
+    pub fn texts_mut(&mut self) -> Vec<&mut Text> {
+        match self {
+            Block::Container(block_container) => block_container
+                .blocks
+                .iter_mut()
+                .flat_map(|block| block.texts_mut())
+                .collect(),
+            Block::Flexible(flexible_container) => flexible_container
+                .blocks
+                .iter_mut()
+                .flat_map(|block| block.texts_mut())
+                .collect(),
+            Block::FlexibleItem(flexible_item) => flexible_item.block.texts_mut(),
+            Block::Wrapper(block_wrapper) => block_wrapper.block.texts_mut(),
+            Block::Text(text) => vec![text],
+            Block::Line(_) | Block::Rectangle(_) | Block::Image(_) => Vec::new(),
+        }
+    }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        match self {
+            Block::Container(block_container) => block_container.set_source_pointer(source_pointer),
+            Block::Flexible(flexible_container) => flexible_container.set_source_pointer(source_pointer),
+            Block::FlexibleItem(flexible_item) => flexible_item.set_source_pointer(source_pointer),
+            Block::Line(line) => line.set_source_pointer(source_pointer),
+            Block::Rectangle(rectangle) => rectangle.set_source_pointer(source_pointer),
+            Block::Text(text) => text.set_source_pointer(source_pointer),
+            Block::Image(image) => image.set_source_pointer(source_pointer),
+            Block::Wrapper(block_wrapper) => block_wrapper.set_source_pointer(source_pointer),
+        }
+    }
+
+    pub fn source_pointer(&self) -> Option<&str> {
+        match self {
+            Block::Container(block_container) => block_container.source_pointer.as_deref(),
+            Block::Flexible(flexible_container) => flexible_container.source_pointer.as_deref(),
+            Block::FlexibleItem(flexible_item) => flexible_item.source_pointer.as_deref(),
+            Block::Line(line) => line.source_pointer.as_deref(),
+            Block::Rectangle(rectangle) => rectangle.source_pointer.as_deref(),
+            Block::Text(text) => text.source_pointer.as_deref(),
+            Block::Image(image) => image.source_pointer.as_deref(),
+            Block::Wrapper(block_wrapper) => block_wrapper.source_pointer.as_deref(),
+        }
+    }
+
+    // NOTE: 入力 JSON の type に対応する名前
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Block::Container(_) => "objects",
+            Block::Flexible(_) => "flexible",
+            Block::FlexibleItem(_) => "flexible_item",
+            Block::Line(_) => "line",
+            Block::Rectangle(_) => "rectangle",
+            Block::Text(_) => "text",
+            Block::Image(_) => "image",
+            Block::Wrapper(_) => "object",
+        }
+    }
+
+    pub fn frame(&self) -> Option<&GeoRect> {
+        match self {
+            Block::Container(block_container) => block_container.frame.as_ref(),
+            Block::Flexible(flexible_container) => flexible_container.frame.as_ref(),
+            Block::FlexibleItem(flexible_item) => flexible_item.frame.as_ref(),
+            Block::Line(line) => Some(&line.frame),
+            Block::Rectangle(rectangle) => rectangle.frame.as_ref(),
+            Block::Text(text) => text.frame.as_ref(),
+            Block::Image(image) => image.frame.as_ref(),
+            Block::Wrapper(block_wrapper) => block_wrapper.frame.as_ref(),
+        }
+    }
+
+    // NOTE: 子要素（frame は親の frame を基準にした座標）
+    pub fn children(&self) -> Vec<&Block> {
+        match self {
+            Block::Container(block_container) => block_container.blocks.iter().collect(),
+            Block::Flexible(flexible_container) => flexible_container.blocks.iter().collect(),
+            Block::FlexibleItem(flexible_item) => vec![&flexible_item.block],
+            Block::Wrapper(block_wrapper) => vec![&block_wrapper.block],
+            Block::Text(_) | Block::Line(_) | Block::Rectangle(_) | Block::Image(_) => Vec::new(),
+        }
+    }
+}
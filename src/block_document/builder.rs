@@ -0,0 +1,627 @@
+use crate::block_document::block::Block;
+use crate::block_document::block_container::BlockContainer;
+use crate::block_document::direction::Direction;
+use crate::block_document::document::Document;
+use crate::block_document::dynamic_page::DynamicPage;
+use crate::block_document::flexible_container::FlexibleContainer;
+use crate::block_document::flexible_item::FlexibleItem;
+use crate::block_document::geometry::{GeoPoint, GeoRect, GeoSize};
+use crate::block_document::image::Image;
+use crate::block_document::line::Line;
+use crate::block_document::page::Page;
+use crate::block_document::page_number::{self, NumberStyle, PageNumber, PageNumbering};
+use crate::block_document::page_size::{self, Orientation};
+use crate::block_document::rectangle::Rectangle;
+use crate::block_document::static_page::StaticPage;
+use crate::block_document::style::{
+    Alignment, BorderStyle, RgbColor, Space, Style, TextOutlineStyle, TextStyle, TextWrap,
+};
+use crate::block_document::text::Text;
+use crate::block_document::wrapper::Wrapper;
+use std::mem::discriminant;
+
+// NOTE: JSON を経由せずに Rust から Document を組み立てる（document_json と同じ Document になる）
+// NOTE: 要素ごとに指定できるスタイルだけをメソッドにしている（JSON ではスキーマで検証している内容）
+// NOTE: 相対パスの font_path や画像の path はカレントディレクトリが基準（AssetPaths による解決はしない）
+
+// NOTE: 同じ種類のスタイルは後から指定したもので置き換える
+fn set_style(styles: &mut Vec<Style>, style: Style) {
+    match styles.iter_mut().find(|current| discriminant(*current) == discriminant(&style)) {
+        Some(current) => *current = style,
+        None => styles.push(style),
+    }
+}
+
+// NOTE: frame の位置だけ、サイズだけの指定（JSON の point / size のみの frame と同じ）
+fn set_point(frame: &mut Option<GeoRect>, x: f32, y: f32) {
+    frame.get_or_insert_with(GeoRect::none).point = Some(GeoPoint { x, y });
+}
+
+fn set_size(frame: &mut Option<GeoRect>, width: f32, height: f32) {
+    frame.get_or_insert_with(GeoRect::none).size = Some(GeoSize { width, height });
+}
+
+macro_rules! frame_methods {
+    ($field:ident) => {
+        pub fn frame(mut self, frame: GeoRect) -> Self {
+            self.$field.frame = Some(frame);
+            self
+        }
+
+        pub fn point(mut self, x: f32, y: f32) -> Self {
+            set_point(&mut self.$field.frame, x, y);
+            self
+        }
+
+        pub fn size(mut self, width: f32, height: f32) -> Self {
+            set_size(&mut self.$field.frame, width, height);
+            self
+        }
+    };
+}
+
+macro_rules! border_methods {
+    ($field:ident) => {
+        pub fn border_color(mut self, color: RgbColor) -> Self {
+            set_style(&mut self.$field.styles, Style::BorderColor(color));
+            self
+        }
+
+        pub fn border_style(mut self, border_style: BorderStyle) -> Self {
+            set_style(&mut self.$field.styles, Style::BorderStyle(border_style));
+            self
+        }
+
+        pub fn border_width(mut self, width: f32) -> Self {
+            set_style(&mut self.$field.styles, Style::BorderWidth(width));
+            self
+        }
+    };
+}
+
+macro_rules! text_color_methods {
+    ($field:ident) => {
+        pub fn text_fill_color(mut self, color: RgbColor) -> Self {
+            set_style(&mut self.$field.styles, Style::TextFillColor(color));
+            self
+        }
+
+        pub fn text_outline_color(mut self, color: RgbColor) -> Self {
+            set_style(&mut self.$field.styles, Style::TextOutlineColor(color));
+            self
+        }
+
+        pub fn text_outline_style(mut self, text_outline_style: TextOutlineStyle) -> Self {
+            set_style(&mut self.$field.styles, Style::TextOutlineStyle(text_outline_style));
+            self
+        }
+
+        pub fn text_style(mut self, text_style: TextStyle) -> Self {
+            set_style(&mut self.$field.styles, Style::TextStyle(text_style));
+            self
+        }
+    };
+}
+
+impl Document {
+    // NOTE: title と font_path は JSON でも必須の項目なので引数で受け取る
+    pub fn builder(title: impl Into<String>, font_path: impl Into<String>) -> DocumentBuilder {
+        DocumentBuilder {
+            title: title.into(),
+            page_size: page_size::A4,
+            orientation: None,
+            margin: Space::default(),
+            font_path: font_path.into(),
+            page_number: None,
+            pages: Vec::new(),
+        }
+    }
+}
+
+enum PageBuilder {
+    Static(StaticPageBuilder),
+    Dynamic(Box<DynamicPageBuilder>),
+}
+
+// NOTE: page_size の初期値は A4
+pub struct DocumentBuilder {
+    title: String,
+    page_size: GeoSize,
+    orientation: Option<Orientation>,
+    margin: Space,
+    font_path: String,
+    page_number: Option<PageNumber>,
+    pages: Vec<PageBuilder>,
+}
+
+impl DocumentBuilder {
+    pub fn page_size(mut self, page_size: GeoSize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    pub fn margin(mut self, margin: Space) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn page_number(mut self, page_number: PageNumberBuilder) -> Self {
+        self.page_number = Some(page_number.build());
+        self
+    }
+
+    pub fn static_page(mut self, f: impl FnOnce(StaticPageBuilder) -> StaticPageBuilder) -> Self {
+        self.pages.push(PageBuilder::Static(f(StaticPageBuilder::new())));
+        self
+    }
+
+    pub fn dynamic_page(mut self, f: impl FnOnce(DynamicPageBuilder) -> DynamicPageBuilder) -> Self {
+        self.pages.push(PageBuilder::Dynamic(Box::from(f(DynamicPageBuilder::new()))));
+        self
+    }
+
+    // NOTE: ページサイズと余白はドキュメントの設定が確定してから決める（dynamic ページの content の frame の初期値のため）
+    pub fn build(self) -> Document {
+        let page_size = page_size::resolve(Some(self.page_size.clone()), self.orientation, &self.page_size).unwrap();
+        let mut document = Document::new(self.title, page_size, self.font_path);
+        document.set_margin(self.margin);
+        if let Some(page_number) = self.page_number {
+            document.set_page_number(page_number);
+        }
+
+        for page in self.pages {
+            let page = match page {
+                PageBuilder::Static(page) => page.build(&document),
+                PageBuilder::Dynamic(page) => page.build(&document),
+            };
+            document.add_page(page);
+        }
+
+        document
+    }
+}
+
+pub struct StaticPageBuilder {
+    page: StaticPage,
+    page_size: Option<GeoSize>,
+    orientation: Option<Orientation>,
+}
+
+impl StaticPageBuilder {
+    fn new() -> StaticPageBuilder {
+        StaticPageBuilder {
+            page: StaticPage::new(),
+            page_size: None,
+            orientation: None,
+        }
+    }
+
+    pub fn page_size(mut self, page_size: GeoSize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    pub fn margin(mut self, margin: Space) -> Self {
+        self.page.set_margin(margin);
+        self
+    }
+
+    pub fn auto_pagination(mut self, auto_pagination: bool) -> Self {
+        self.page.set_auto_pagination(auto_pagination);
+        self
+    }
+
+    pub fn page_numbering(mut self, page_numbering: PageNumbering) -> Self {
+        self.page.set_page_numbering(page_numbering);
+        self
+    }
+
+    pub fn object(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_block(block.into());
+        self
+    }
+
+    fn build(mut self, document: &Document) -> Page {
+        if let Some(page_size) = page_size::resolve(self.page_size, self.orientation, &document.page_size) {
+            self.page.set_page_size(page_size);
+        }
+
+        Page::StaticPage(self.page)
+    }
+}
+
+pub struct DynamicPageBuilder {
+    page: DynamicPage,
+    page_size: Option<GeoSize>,
+    orientation: Option<Orientation>,
+    content_frame: Option<GeoRect>,
+}
+
+impl DynamicPageBuilder {
+    fn new() -> DynamicPageBuilder {
+        DynamicPageBuilder {
+            page: DynamicPage::new(),
+            page_size: None,
+            orientation: None,
+            content_frame: None,
+        }
+    }
+
+    pub fn page_size(mut self, page_size: GeoSize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    pub fn margin(mut self, margin: Space) -> Self {
+        self.page.set_margin(margin);
+        self
+    }
+
+    pub fn page_numbering(mut self, page_numbering: PageNumbering) -> Self {
+        self.page.set_page_numbering(page_numbering);
+        self
+    }
+
+    // NOTE: 未指定の場合は余白を除いたページ全体
+    pub fn content_frame(mut self, content_frame: GeoRect) -> Self {
+        self.content_frame = Some(content_frame);
+        self
+    }
+
+    pub fn content(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_content_block(block.into());
+        self
+    }
+
+    pub fn common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_common_block(block.into());
+        self
+    }
+
+    // NOTE: 2ページ目以降の content の frame
+    pub fn continuation_content_frame(mut self, content_frame: GeoRect) -> Self {
+        self.page.set_continuation_content_frame(content_frame);
+        self
+    }
+
+    // NOTE: 2ページ目以降は common の代わりに描画する
+    pub fn continuation_common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_continuation_common_block(block.into());
+        self
+    }
+
+    pub fn first_common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_first_common_block(block.into());
+        self
+    }
+
+    pub fn last_common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_last_common_block(block.into());
+        self
+    }
+
+    pub fn odd_common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_odd_common_block(block.into());
+        self
+    }
+
+    pub fn odd_content_frame(mut self, content_frame: GeoRect) -> Self {
+        self.page.set_odd_content_frame(content_frame);
+        self
+    }
+
+    pub fn even_common(mut self, block: impl Into<Block>) -> Self {
+        self.page.add_even_common_block(block.into());
+        self
+    }
+
+    pub fn even_content_frame(mut self, content_frame: GeoRect) -> Self {
+        self.page.set_even_content_frame(content_frame);
+        self
+    }
+
+    fn build(mut self, document: &Document) -> Page {
+        if let Some(page_size) = page_size::resolve(self.page_size, self.orientation, &document.page_size) {
+            self.page.set_page_size(page_size);
+        }
+
+        let content_frame = self.content_frame.unwrap_or_else(|| {
+            let page_size = self.page.page_size.clone().unwrap_or(document.page_size.clone());
+            GeoRect::new(page_size.width, page_size.height, 0.0, 0.0)
+                .inset(self.page.margin.as_ref().unwrap_or(&document.margin))
+        });
+        self.page.set_content_frame(content_frame);
+
+        Page::DynamicPage(Box::new(self.page))
+    }
+}
+
+impl PageNumber {
+    pub fn builder(font_size: f32) -> PageNumberBuilder {
+        PageNumberBuilder {
+            page_number: PageNumber::new(page_number::DEFAULT_FORMAT.to_string(), font_size, None, None),
+        }
+    }
+}
+
+pub struct PageNumberBuilder {
+    page_number: PageNumber,
+}
+
+impl PageNumberBuilder {
+    frame_methods!(page_number);
+    text_color_methods!(page_number);
+
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.page_number.format = format.into();
+        self
+    }
+
+    pub fn font_path(mut self, font_path: impl Into<String>) -> Self {
+        self.page_number.font_path = Some(font_path.into());
+        self
+    }
+
+    pub fn number_style(mut self, number_style: NumberStyle) -> Self {
+        self.page_number.set_number_style(number_style);
+        self
+    }
+
+    pub fn build(self) -> PageNumber {
+        self.page_number
+    }
+}
+
+impl Text {
+    pub fn builder(text: impl Into<String>, font_size: f32) -> TextBuilder {
+        TextBuilder {
+            text: Text::new(text.into(), font_size, None, None),
+        }
+    }
+}
+
+pub struct TextBuilder {
+    text: Text,
+}
+
+impl TextBuilder {
+    frame_methods!(text);
+    border_methods!(text);
+    text_color_methods!(text);
+
+    // NOTE: 未指定の場合はドキュメントの font_path
+    pub fn font_path(mut self, font_path: impl Into<String>) -> Self {
+        self.text.font_path = Some(font_path.into());
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        set_style(&mut self.text.styles, Style::Alignment(alignment));
+        self
+    }
+
+    pub fn text_wrap(mut self, text_wrap: TextWrap) -> Self {
+        set_style(&mut self.text.styles, Style::TextWrap(text_wrap));
+        self
+    }
+
+    pub fn build(self) -> Text {
+        self.text
+    }
+}
+
+impl Image {
+    pub fn builder(path: impl Into<String>) -> ImageBuilder {
+        ImageBuilder {
+            image: Image::new(path.into(), None),
+        }
+    }
+}
+
+pub struct ImageBuilder {
+    image: Image,
+}
+
+impl ImageBuilder {
+    frame_methods!(image);
+    border_methods!(image);
+
+    pub fn build(self) -> Image {
+        self.image
+    }
+}
+
+impl Rectangle {
+    pub fn builder() -> RectangleBuilder {
+        RectangleBuilder {
+            rectangle: Rectangle::new(None),
+        }
+    }
+}
+
+pub struct RectangleBuilder {
+    rectangle: Rectangle,
+}
+
+impl RectangleBuilder {
+    frame_methods!(rectangle);
+    border_methods!(rectangle);
+
+    pub fn background_color(mut self, color: RgbColor) -> Self {
+        set_style(&mut self.rectangle.styles, Style::BackgroundColor(color));
+        self
+    }
+
+    pub fn build(self) -> Rectangle {
+        self.rectangle
+    }
+}
+
+impl Line {
+    // NOTE: 線は始点（x, y）からの長さ（width, height）で指定する
+    pub fn builder(x: f32, y: f32, width: f32, height: f32) -> LineBuilder {
+        LineBuilder {
+            line: Line::new(GeoRect::new(width, height, x, y)),
+        }
+    }
+}
+
+pub struct LineBuilder {
+    line: Line,
+}
+
+impl LineBuilder {
+    border_methods!(line);
+
+    pub fn space(mut self, space: Space) -> Self {
+        set_style(&mut self.line.styles, Style::Space(space));
+        self
+    }
+
+    pub fn build(self) -> Line {
+        self.line
+    }
+}
+
+impl BlockContainer {
+    pub fn builder() -> BlockContainerBuilder {
+        BlockContainerBuilder {
+            container: BlockContainer::new(None),
+        }
+    }
+}
+
+pub struct BlockContainerBuilder {
+    container: BlockContainer,
+}
+
+impl BlockContainerBuilder {
+    frame_methods!(container);
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.container.set_direction(direction);
+        self
+    }
+
+    pub fn object(mut self, block: impl Into<Block>) -> Self {
+        self.container.add_block(block.into());
+        self
+    }
+
+    pub fn build(self) -> BlockContainer {
+        self.container
+    }
+}
+
+impl FlexibleContainer {
+    pub fn builder() -> FlexibleContainerBuilder {
+        FlexibleContainerBuilder {
+            container: FlexibleContainer::new(None),
+        }
+    }
+}
+
+pub struct FlexibleContainerBuilder {
+    container: FlexibleContainer,
+}
+
+impl FlexibleContainerBuilder {
+    frame_methods!(container);
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.container.set_direction(direction);
+        self
+    }
+
+    // NOTE: basis はコンテナの幅（高さ）に対する割合（%）。指定しない場合は子要素の数で等分する
+    pub fn item(mut self, block: impl Into<Block>, basis: Option<f32>) -> Self {
+        self.container
+            .add_block(Block::FlexibleItem(Box::from(FlexibleItem::new(block.into(), basis))));
+        self
+    }
+
+    pub fn build(self) -> FlexibleContainer {
+        self.container
+    }
+}
+
+// NOTE: JSON の "object"（space で子要素の外側に余白を付ける）
+impl Wrapper {
+    pub fn builder(block: impl Into<Block>) -> WrapperBuilder {
+        WrapperBuilder {
+            wrapper: Wrapper::new(block.into()),
+        }
+    }
+}
+
+pub struct WrapperBuilder {
+    wrapper: Wrapper,
+}
+
+impl WrapperBuilder {
+    pub fn space(mut self, space: Space) -> Self {
+        set_style(&mut self.wrapper.styles, Style::Space(space));
+        self
+    }
+
+    pub fn build(self) -> Wrapper {
+        self.wrapper
+    }
+}
+
+impl From<TextBuilder> for Block {
+    fn from(builder: TextBuilder) -> Block {
+        Block::Text(builder.build())
+    }
+}
+
+impl From<ImageBuilder> for Block {
+    fn from(builder: ImageBuilder) -> Block {
+        Block::Image(builder.build())
+    }
+}
+
+impl From<RectangleBuilder> for Block {
+    fn from(builder: RectangleBuilder) -> Block {
+        Block::Rectangle(builder.build())
+    }
+}
+
+impl From<LineBuilder> for Block {
+    fn from(builder: LineBuilder) -> Block {
+        Block::Line(builder.build())
+    }
+}
+
+impl From<BlockContainerBuilder> for Block {
+    fn from(builder: BlockContainerBuilder) -> Block {
+        Block::Container(builder.build())
+    }
+}
+
+impl From<FlexibleContainerBuilder> for Block {
+    fn from(builder: FlexibleContainerBuilder) -> Block {
+        Block::Flexible(builder.build())
+    }
+}
+
+impl From<WrapperBuilder> for Block {
+    fn from(builder: WrapperBuilder) -> Block {
+        Block::Wrapper(Box::from(builder.build()))
+    }
+}
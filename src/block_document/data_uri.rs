@@ -0,0 +1,40 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+// NOTE: 画像やフォントを JSON に埋め込むための data URI（例: data:image/png;base64,...）
+const DATA_URI_PREFIX: &str = "data:";
+const BASE64_SUFFIX: &str = ";base64";
+
+// NOTE: エラーメッセージに表示する最大文字数
+const DISPLAY_LENGTH: usize = 48;
+
+pub fn is_data_uri(path: &str) -> bool {
+    path.starts_with(DATA_URI_PREFIX)
+}
+
+// NOTE: base64 でエンコードされたもののみ対応する（改行や空白は無視する）
+pub fn decode(uri: &str) -> Result<Vec<u8>, String> {
+    let Some((header, data)) = uri
+        .strip_prefix(DATA_URI_PREFIX)
+        .and_then(|uri| uri.split_once(','))
+    else {
+        return Err("invalid data URI".to_string());
+    };
+
+    if !header.ends_with(BASE64_SUFFIX) {
+        return Err("only base64 encoded data URIs are supported".to_string());
+    }
+
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD.decode(data).map_err(|e| e.to_string())
+}
+
+// NOTE: data URI はエラーメッセージが長くなりすぎないように省略する
+pub fn display(path: &str) -> String {
+    if !is_data_uri(path) || path.chars().count() <= DISPLAY_LENGTH {
+        return path.to_string();
+    }
+
+    let head: String = path.chars().take(DISPLAY_LENGTH).collect();
+    format!("{}... ({} bytes)", head, path.len())
+}
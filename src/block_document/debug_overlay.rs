@@ -0,0 +1,184 @@
+use crate::block_document::block::Block;
+use crate::block_document::geometry::GeoRect;
+use crate::block_document::page::Page;
+use crate::block_document::pdf_writer::{text_baselines, text_offset};
+use crate::block_document::style::{Space, Style};
+use printpdf::{
+    BlendMode, Color, IndirectFontRef, Line, LineDashPattern, Mm, PdfDocumentReference,
+    PdfLayerReference, PdfPageIndex, Point, Rect, Rgb, SeperableBlendMode,
+};
+
+// NOTE: ビューアのレイヤー（オプショナルコンテンツ）の一覧に表示される名前
+const LAYER_NAME: &str = "Debug";
+
+const LINE_THICKNESS: f32 = 0.3; // NOTE: pt
+const LABEL_FONT_SIZE: f32 = 3.0; // NOTE: pt
+const LABEL_MARGIN: f32 = 0.3; // NOTE: mm
+const LABEL_HEIGHT: f32 = 1.1; // NOTE: mm
+
+const CONTENT_FRAME_COLOR: (u8, u8, u8) = (0, 160, 0);
+const PADDING_COLOR: (u8, u8, u8) = (255, 230, 160);
+const BASELINE_COLOR: (u8, u8, u8) = (255, 0, 160);
+const LABEL_COLOR: (u8, u8, u8) = (90, 90, 90);
+
+// NOTE: --debug の表示（ブロックの frame・余白・ベースライン・JSON ポインタ）
+// NOTE: 1つのレイヤーにまとめて描画する（ビューアで非表示にできるように）
+pub fn draw(
+    doc: &PdfDocumentReference,
+    page_index: &PdfPageIndex,
+    page_frame: &GeoRect,
+    page: &Page,
+    font: &IndirectFontRef,
+) {
+    let layer = doc.get_page(*page_index).add_layer(LAYER_NAME);
+    // NOTE: 塗りつぶしで下のオブジェクトが隠れないように乗算で合成する
+    layer.set_blend_mode(BlendMode::Seperable(SeperableBlendMode::Multiply));
+    layer.set_outline_thickness(LINE_THICKNESS);
+
+    if let Some(content_frame) = page.content_frame() {
+        layer.set_line_dash_pattern(LineDashPattern {
+            dash_1: Some(3),
+            ..Default::default()
+        });
+        stroke_rect(&layer, &content_frame.transform(page_frame), CONTENT_FRAME_COLOR);
+        layer.set_line_dash_pattern(LineDashPattern::default());
+    }
+
+    for block in page.drawn_blocks() {
+        draw_block(&layer, page_frame, font, block);
+    }
+}
+
+// NOTE: parent_frame の基準点は左下（pdf_writer::draw と同じ）
+fn draw_block(layer: &PdfLayerReference, parent_frame: &GeoRect, font: &IndirectFontRef, block: &Block) {
+    let lb_frame = block.frame().unwrap_or(&GeoRect::none()).transform(parent_frame);
+
+    // NOTE: 位置が確定していないブロックは描画されないので表示しない
+    if block.frame().is_some_and(|frame| frame.point.is_some()) {
+        if let Block::Wrapper(block_wrapper) = block
+            && let Some(frame) = &block_wrapper.frame
+        {
+            fill_padding(layer, parent_frame, frame, &block_wrapper.styles);
+        }
+
+        stroke_rect(layer, &lb_frame, color(block));
+
+        if let Block::Text(text) = block {
+            let (x_offset, y_offset) = text_offset(text, parent_frame);
+            for y in text_baselines(text, &lb_frame, y_offset) {
+                add_line(
+                    layer,
+                    (lb_frame.min_x() + x_offset, y),
+                    (lb_frame.max_x() + x_offset, y),
+                    BASELINE_COLOR,
+                );
+            }
+        }
+
+        if let Some(source_pointer) = block.source_pointer() {
+            layer.set_fill_color(rgb(LABEL_COLOR));
+            layer.use_text(
+                source_pointer,
+                LABEL_FONT_SIZE,
+                Mm(lb_frame.min_x() + LABEL_MARGIN),
+                Mm(lb_frame.max_y() - LABEL_HEIGHT),
+                font,
+            );
+        }
+    }
+
+    for child in block.children() {
+        draw_block(layer, &lb_frame, font, child);
+    }
+}
+
+// NOTE: レイアウトで frame の左上を余白の分だけずらして大きさを広げているので、その外周を塗る
+fn fill_padding(layer: &PdfLayerReference, parent_frame: &GeoRect, frame: &GeoRect, styles: &[Style]) {
+    let mut space = Space::default();
+    for style in styles {
+        if let Style::Space(s) = style {
+            space.top += s.top;
+            space.right += s.right;
+            space.bottom += s.bottom;
+            space.left += s.left;
+        }
+    }
+
+    if space.top == 0.0 && space.right == 0.0 && space.bottom == 0.0 && space.left == 0.0 {
+        return;
+    }
+
+    let outer = GeoRect::new(
+        frame.width(),
+        frame.height(),
+        frame.min_x() - space.left,
+        frame.min_y() - space.top,
+    )
+    .transform(parent_frame);
+    let inner = GeoRect::new(
+        frame.width() - space.left - space.right,
+        frame.height() - space.top - space.bottom,
+        frame.min_x(),
+        frame.min_y(),
+    )
+    .transform(parent_frame);
+
+    layer.set_fill_color(rgb(PADDING_COLOR));
+    for (min_x, min_y, max_x, max_y) in [
+        (outer.min_x(), inner.max_y(), outer.max_x(), outer.max_y()), // NOTE: 上
+        (outer.min_x(), outer.min_y(), outer.max_x(), inner.min_y()), // NOTE: 下
+        (outer.min_x(), inner.min_y(), inner.min_x(), inner.max_y()), // NOTE: 左
+        (inner.max_x(), inner.min_y(), outer.max_x(), inner.max_y()), // NOTE: 右
+    ] {
+        if max_x > min_x && max_y > min_y {
+            layer.add_rect(Rect::new(Mm(min_x), Mm(min_y), Mm(max_x), Mm(max_y)));
+        }
+    }
+}
+
+// NOTE: Block の種類ごとの枠の色
+fn color(block: &Block) -> (u8, u8, u8) {
+    match block {
+        Block::Container(_) => (0, 90, 255),
+        Block::Flexible(_) => (140, 0, 255),
+        Block::FlexibleItem(_) => (200, 0, 200),
+        Block::Wrapper(_) => (255, 140, 0),
+        Block::Text(_) => (230, 0, 0),
+        Block::Image(_) => (0, 170, 170),
+        Block::Rectangle(_) => (120, 120, 0),
+        Block::Line(_) => (120, 70, 20),
+    }
+}
+
+fn rgb((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb(Rgb {
+        r: r as f32 / 255.0,
+        g: g as f32 / 255.0,
+        b: b as f32 / 255.0,
+        icc_profile: None,
+    })
+}
+
+fn stroke_rect(layer: &PdfLayerReference, lb_frame: &GeoRect, color: (u8, u8, u8)) {
+    layer.set_outline_color(rgb(color));
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(lb_frame.min_x()), Mm(lb_frame.min_y())), false),
+            (Point::new(Mm(lb_frame.max_x()), Mm(lb_frame.min_y())), false),
+            (Point::new(Mm(lb_frame.max_x()), Mm(lb_frame.max_y())), false),
+            (Point::new(Mm(lb_frame.min_x()), Mm(lb_frame.max_y())), false),
+        ],
+        is_closed: true,
+    });
+}
+
+fn add_line(layer: &PdfLayerReference, from: (f32, f32), to: (f32, f32), color: (u8, u8, u8)) {
+    layer.set_outline_color(rgb(color));
+    layer.add_line(Line {
+        points: vec![
+            (Point::new(Mm(from.0), Mm(from.1)), false),
+            (Point::new(Mm(to.0), Mm(to.1)), false),
+        ],
+        is_closed: false,
+    });
+}
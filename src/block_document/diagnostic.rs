@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::process::exit;
+
+// NOTE: JSON ポインタから入力ファイル上の位置を引く（エラーメッセージに行・列を表示するため）
+pub struct SourceMap {
+    path: String,
+    source: String,
+    offsets: HashMap<String, usize>, // NOTE: JSON ポインタ → 値の開始位置（バイト）
+}
+
+struct Location<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl SourceMap {
+    // NOTE: JSON として読めない場合（YAML / TOML / JSON5）は None
+    pub fn new(path: &str, source: &str) -> Option<SourceMap> {
+        let mut scanner = Scanner {
+            bytes: source.as_bytes(),
+            position: 0,
+            offsets: HashMap::new(),
+        };
+        scanner.scan_value(String::new())?;
+
+        Some(SourceMap {
+            path: path.to_string(),
+            source: source.to_string(),
+            offsets: scanner.offsets,
+        })
+    }
+
+    // NOTE: テンプレートやコンポーネントの展開で元の位置がない場合は親の位置を返す
+    fn location(&self, pointer: &str) -> Option<Location<'_>> {
+        let mut pointer = pointer;
+        let offset = loop {
+            if let Some(offset) = self.offsets.get(pointer) {
+                break *offset;
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        };
+
+        let line_start = self.source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[offset..].find('\n').map_or(self.source.len(), |i| offset + i);
+
+        Some(Location {
+            line: self.source[..offset].matches('\n').count() + 1,
+            column: self.source[line_start..offset].chars().count() + 1,
+            text: self.source[line_start..line_end].trim_end_matches('\r'),
+        })
+    }
+}
+
+// NOTE: 解析済みの JSON の各値の位置を記録する（構文は serde_json で検証済みの前提）
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    offsets: HashMap<String, usize>,
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.position).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn scan_value(&mut self, pointer: String) -> Option<()> {
+        self.skip_whitespace();
+        self.offsets.insert(pointer.clone(), self.position);
+
+        match self.bytes.get(self.position)? {
+            b'{' => {
+                self.position += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.position)? {
+                        b'}' => {
+                            self.position += 1;
+                            return Some(());
+                        }
+                        b',' => self.position += 1,
+                        b'"' => {
+                            let key = self.scan_string()?;
+                            self.skip_whitespace();
+                            if *self.bytes.get(self.position)? != b':' {
+                                return None;
+                            }
+                            self.position += 1;
+                            self.scan_value(format!("{}/{}", pointer, escape(&key)))?;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            b'[' => {
+                self.position += 1;
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.bytes.get(self.position)? {
+                        b']' => {
+                            self.position += 1;
+                            return Some(());
+                        }
+                        b',' => self.position += 1,
+                        _ => {
+                            self.scan_value(format!("{}/{}", pointer, index))?;
+                            index += 1;
+                        }
+                    }
+                }
+            }
+            b'"' => self.scan_string().map(|_| ()),
+            _ => {
+                // NOTE: 数値・true・false・null
+                while self
+                    .bytes
+                    .get(self.position)
+                    .is_some_and(|byte| !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace())
+                {
+                    self.position += 1;
+                }
+
+                Some(())
+            }
+        }
+    }
+
+    fn scan_string(&mut self) -> Option<String> {
+        let start = self.position;
+        self.position += 1;
+        loop {
+            match self.bytes.get(self.position)? {
+                b'\\' => self.position += 2,
+                b'"' => {
+                    self.position += 1;
+                    break;
+                }
+                _ => self.position += 1,
+            }
+        }
+
+        serde_json::from_slice(&self.bytes[start..self.position]).ok()
+    }
+}
+
+// NOTE: JSON ポインタのエスケープ（RFC 6901）
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+// NOTE: 入力の誤りをまとめて報告する（1件ずつ修正しなくて済むように）
+pub struct Diagnostics<'a> {
+    source_map: Option<&'a SourceMap>,
+    entries: RefCell<Vec<(Severity, String, String)>>, // NOTE: (重要度, JSON ポインタ, メッセージ)
+}
+
+impl<'a> Diagnostics<'a> {
+    pub fn new(source_map: Option<&'a SourceMap>) -> Diagnostics<'a> {
+        Diagnostics {
+            source_map,
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn error(&self, pointer: &str, message: impl Display) {
+        self.entries
+            .borrow_mut()
+            .push((Severity::Error, pointer.to_string(), message.to_string()));
+    }
+
+    // NOTE: PDF は出力できるが意図しない結果になりそうなもの（レイアウトの検証で使用）
+    pub fn warning(&self, pointer: &str, message: impl Display) {
+        self.entries
+            .borrow_mut()
+            .push((Severity::Warning, pointer.to_string(), message.to_string()));
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.entries.borrow().iter().filter(|(entry_severity, _, _)| *entry_severity == severity).count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(Severity::Error) > 0
+    }
+
+    // NOTE: (重要度, JSON ポインタ, メッセージ)（サーバーモードのレスポンス用）
+    pub fn entries(&self) -> Vec<(Severity, String, String)> {
+        self.entries.borrow().clone()
+    }
+
+    // NOTE: すべて表示する
+    pub fn report(&self) {
+        let entries = self.entries.borrow();
+        if entries.is_empty() {
+            return;
+        }
+
+        for (severity, pointer, message) in entries.iter() {
+            eprintln!("{}", self.format(severity, pointer, message));
+        }
+        eprintln!(
+            "{} error(s), {} warning(s) found",
+            self.count(Severity::Error),
+            self.count(Severity::Warning)
+        );
+    }
+
+    // NOTE: 先頭に prefix を付けてすべて表示する（バッチ処理のレコード番号など）
+    pub fn report_with_prefix(&self, prefix: &str) {
+        for (severity, pointer, message) in self.entries.borrow().iter() {
+            eprintln!("{}: {}", prefix, self.format(severity, pointer, message));
+        }
+    }
+
+    // NOTE: エラーがあればすべて表示して終了する
+    pub fn exit_if_errors(&self) {
+        if !self.has_errors() {
+            return;
+        }
+
+        self.report();
+        exit(1);
+    }
+
+    fn format(&self, severity: &Severity, pointer: &str, message: &str) -> String {
+        let label = match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let at = if pointer.is_empty() { "/" } else { pointer };
+
+        let Some((path, location)) = self
+            .source_map
+            .and_then(|source_map| Some((source_map.path.as_str(), source_map.location(pointer)?)))
+        else {
+            return format!("{}: {}\n  = at {}\n", label, message, at);
+        };
+
+        let gutter = " ".repeat(location.line.to_string().len());
+        format!(
+            "{}: {}\n{gutter}--> {}:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}^\n{gutter} = at {}\n",
+            label,
+            message,
+            path,
+            location.line,
+            location.column,
+            location.line,
+            location.text,
+            " ".repeat(location.column - 1),
+            at,
+        )
+    }
+}
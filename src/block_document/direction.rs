@@ -1,4 +1,9 @@
-#[derive(Debug, Clone, PartialEq)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "direction")]
 pub enum Direction {
     Horizontal,
     Vertical,
@@ -1,6 +1,9 @@
 use crate::block_document::geometry::GeoSize;
 use crate::block_document::page::Page;
 use crate::block_document::page_number::PageNumber;
+use crate::block_document::style::Space;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 pub const DPI: f32 = 300.0;
 
@@ -8,10 +11,14 @@ pub fn px_to_mm(pixel: f32) -> f32 {
     pixel / (DPI / 25.4)
 }
 
-#[derive(Clone)]
+// NOTE: schema.json の形式でシリアライズする（document_serde）。schema.json もこの型から生成する（document_schema）
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "crate::block_document::document_serde::DocumentJson")]
+#[serde(into = "crate::block_document::document_serde::DocumentJson")]
 pub struct Document {
     pub title: String,
     pub page_size: GeoSize,
+    pub margin: Space,
     pub font_path: String,
     pub page_number: Option<PageNumber>,
     pub pages: Vec<Page>,
@@ -22,12 +29,17 @@ impl Document {
         Document {
             title,
             page_size,
+            margin: Space::default(),
             font_path,
             page_number: None,
             pages: Vec::new(),
         }
     }
 
+    pub fn set_margin(&mut self, margin: Space) {
+        self.margin = margin;
+    }
+
     pub fn set_page_number(&mut self, page_number: PageNumber) {
         self.page_number = Some(page_number);
     }
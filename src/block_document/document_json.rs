@@ -1,28 +1,24 @@
+use crate::block_document::asset_path::AssetPaths;
 use crate::block_document::block::Block;
-use crate::block_document::block_container::BlockContainer;
-use crate::block_document::direction::Direction;
+use crate::block_document::data_uri;
+use crate::block_document::diagnostic::{Diagnostics, SourceMap};
 use crate::block_document::document::Document;
-use crate::block_document::dynamic_page::DynamicPage;
-use crate::block_document::flexible_container::FlexibleContainer;
-use crate::block_document::flexible_item::FlexibleItem;
-use crate::block_document::geometry::{GeoPoint, GeoRect, GeoSize};
-use crate::block_document::image::Image;
-use crate::block_document::line::Line;
+use crate::block_document::document_schema;
+use crate::block_document::document_serde::StyleClassJson;
+use crate::block_document::input_format::{self, InputFormat};
 use crate::block_document::page::Page;
-use crate::block_document::page_number::PageNumber;
-use crate::block_document::rectangle::Rectangle;
-use crate::block_document::static_page::StaticPage;
-use crate::block_document::style::{
-    Alignment, BorderStyle, HorizontalAlignment, RgbColor, Space, Style, TextOutlineStyle,
-    TextOverflow, TextStyle, TextWrap, TextWrapMode, VerticalAlignment,
-};
-use crate::block_document::text::Text;
-use crate::block_document::wrapper::Wrapper;
-use serde_json::Value;
-use std::fs::read_to_string;
-use std::process::exit;
-
-const PAGE_NUMBER_FORMAT: &'static str = "Page ${CURRENT_PAGE_NUMBER} of ${TOTAL_PAGES}";
+use crate::block_document::page_size::NAMED_PAGE_SIZES;
+use crate::block_document::resource::{load_font, load_image};
+use crate::block_document::template;
+use input_format::STDIN_PATH;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::Validator;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
 const PAGE_TYPE_DYNAMIC: &'static str = "dynamic";
 const PAGE_TYPE_STATIC: &'static str = "static";
 const OBJECT_TYPE_TEXT: &'static str = "text";
@@ -33,613 +29,760 @@ const OBJECT_TYPE_OBJECT: &'static str = "object";
 const OBJECT_TYPE_OBJECTS: &'static str = "objects";
 const OBJECT_TYPE_FLEXIBLE: &'static str = "flexible";
 const OBJECT_TYPE_FLEXIBLE_ITEM: &'static str = "flexible_item";
+const OBJECT_TYPE_COMPONENT: &'static str = "component";
+
+// NOTE: anyOf のエラーを詳しく報告するため、type ごとに検証し直す定義
+const TYPE_DEFINITIONS: &[(&str, &str)] = &[
+    (PAGE_TYPE_STATIC, "#/definitions/static_page"),
+    (PAGE_TYPE_DYNAMIC, "#/definitions/dynamic_page"),
+    (OBJECT_TYPE_TEXT, "#/definitions/text"),
+    (OBJECT_TYPE_IMAGE, "#/definitions/image"),
+    (OBJECT_TYPE_LINE, "#/definitions/line"),
+    (OBJECT_TYPE_RECTANGLE, "#/definitions/rectangle"),
+    (OBJECT_TYPE_OBJECT, "#/definitions/object"),
+    (OBJECT_TYPE_OBJECTS, "#/definitions/objects"),
+    (OBJECT_TYPE_FLEXIBLE, "#/definitions/flexible"),
+    (OBJECT_TYPE_FLEXIBLE_ITEM, "#/definitions/flexible_item"),
+    (OBJECT_TYPE_COMPONENT, "#/definitions/component"),
+];
+const ANY_OBJECT_DEFINITION: &str = "#/definitions/any_object";
+// NOTE: null を許可する frame の point / size（oneOf）のエラーを詳しく報告するため、値ごとに検証し直す定義
+const NULLABLE_DEFINITIONS: &[(&str, &str)] = &[("point", "#/definitions/point"), ("size", "#/definitions/size")];
+
+pub(crate) const STYLE_KEY_ALIGNMENT: &str = "alignment";
+pub(crate) const STYLE_KEY_BACKGROUND_COLOR: &str = "background_color";
+pub(crate) const STYLE_KEY_BORDER_COLOR: &str = "border_color";
+pub(crate) const STYLE_KEY_BORDER_STYLE: &str = "border_style";
+pub(crate) const STYLE_KEY_BORDER_WIDTH: &str = "border_width";
+pub(crate) const STYLE_KEY_FONT_PATH: &str = "font_path";
+pub(crate) const STYLE_KEY_FONT_SIZE: &str = "font_size";
+pub(crate) const STYLE_KEY_SPACE: &str = "space";
+pub(crate) const STYLE_KEY_TEXT_FILL_COLOR: &str = "text_fill_color";
+pub(crate) const STYLE_KEY_TEXT_OUTLINE_COLOR: &str = "text_outline_color";
+pub(crate) const STYLE_KEY_TEXT_OUTLINE_STYLE: &str = "text_outline_style";
+pub(crate) const STYLE_KEY_TEXT_STYLE: &str = "text_style";
+pub(crate) const STYLE_KEY_TEXT_WRAP: &str = "text_wrap";
+
+pub(crate) const TEXT_STYLE_KEYS: &[&str] = &[
+    STYLE_KEY_ALIGNMENT,
+    STYLE_KEY_BORDER_COLOR,
+    STYLE_KEY_BORDER_STYLE,
+    STYLE_KEY_BORDER_WIDTH,
+    STYLE_KEY_TEXT_FILL_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_STYLE,
+    STYLE_KEY_TEXT_STYLE,
+    STYLE_KEY_TEXT_WRAP,
+];
+pub(crate) const IMAGE_STYLE_KEYS: &[&str] = &[STYLE_KEY_BORDER_COLOR, STYLE_KEY_BORDER_STYLE, STYLE_KEY_BORDER_WIDTH];
+pub(crate) const LINE_STYLE_KEYS: &[&str] = &[
+    STYLE_KEY_SPACE,
+    STYLE_KEY_BORDER_COLOR,
+    STYLE_KEY_BORDER_STYLE,
+    STYLE_KEY_BORDER_WIDTH,
+];
+pub(crate) const RECTANGLE_STYLE_KEYS: &[&str] = &[
+    STYLE_KEY_BACKGROUND_COLOR,
+    STYLE_KEY_BORDER_COLOR,
+    STYLE_KEY_BORDER_STYLE,
+    STYLE_KEY_BORDER_WIDTH,
+];
+pub(crate) const WRAPPER_STYLE_KEYS: &[&str] = &[STYLE_KEY_SPACE];
+pub(crate) const PAGE_NUMBER_STYLE_KEYS: &[&str] = &[
+    STYLE_KEY_TEXT_FILL_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_STYLE,
+    STYLE_KEY_TEXT_STYLE,
+];
+// NOTE: コンテナから子要素に継承されるスタイル
+pub(crate) const INHERITED_STYLE_KEYS: &[&str] = &[
+    STYLE_KEY_FONT_PATH,
+    STYLE_KEY_FONT_SIZE,
+    STYLE_KEY_TEXT_FILL_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_COLOR,
+    STYLE_KEY_TEXT_OUTLINE_STYLE,
+    STYLE_KEY_TEXT_STYLE,
+];
+
+// NOTE: スキーマは document_serde の型から生成する（schema/schema.json と同じ内容）
+fn json_schema() -> &'static Value {
+    static JSON_SCHEMA: OnceLock<Value> = OnceLock::new();
+
+    JSON_SCHEMA.get_or_init(document_schema::generate)
+}
 
-const JSON_SCHEMA_BYTES: &'static [u8] = include_bytes!("../../schema/schema.json");
-
-// NOTE: BlockDocument の座標基準は左上（printpdf は左下）
-pub fn parse(json_path: &str) -> Document {
-    let json_string = read_to_string(json_path).unwrap();
-    let json: Value = serde_json::from_str(&json_string).unwrap();
-    let schema = serde_json::from_slice(JSON_SCHEMA_BYTES).unwrap();
-    let validator = jsonschema::validator_for(&schema).unwrap();
-
-    if !validator.validate(&json).is_ok() {
-        eprintln!("Invalid schema");
-        for error in validator.iter_errors(&json) {
-            eprintln!("Error: {error}");
-            eprintln!("Location: {}", error.instance_path);
-        }
-        exit(1);
-    }
+// NOTE: スキーマの読み込みは1度だけ行う（バッチ処理で繰り返し解析する場合のため）
+fn validator() -> &'static Validator {
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
 
-    let mut doc = Document::new(
-        json["document"]["title"].as_str().unwrap().to_string(),
-        GeoSize {
-            width: json["document"]["width"].as_f64().unwrap() as f32,
-            height: json["document"]["height"].as_f64().unwrap() as f32,
-        },
-        json["document"]["font_path"].as_str().unwrap().to_string(),
-    );
-
-    let page_number_json = &json["document"]["page_number"];
-    if let Some(page_number) = parse_page_number(page_number_json) {
-        doc.set_page_number(page_number);
-    }
+    VALIDATOR.get_or_init(|| jsonschema::validator_for(json_schema()).unwrap())
+}
 
-    json["document"]["pages"]
-        .as_array()
-        .unwrap()
-        .iter()
-        .for_each(|page_json| match page_json["type"].as_str().unwrap() {
-            PAGE_TYPE_STATIC => {
-                let mut page = StaticPage::new();
-
-                let auto_pagination = page_json["auto_pagination"].as_bool().unwrap_or(false);
-                page.set_auto_pagination(auto_pagination);
-
-                page_json["objects"]
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .for_each(|object_json| {
-                        if let Some(object) = parse_object(object_json) {
-                            page.add_block(object);
-                        }
-                    });
-
-                doc.add_page(Page::StaticPage(page));
-            }
-            PAGE_TYPE_DYNAMIC => {
-                let mut page = DynamicPage::new();
-
-                page_json["common"]["objects"]
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .for_each(|object_json| {
-                        if let Some(object) = parse_object(object_json) {
-                            page.add_common_block(object);
-                        }
-                    });
-
-                let frame = parse_frame(&page_json["content"]["frame"]);
-                page.set_content_frame(frame);
-
-                page_json["content"]["objects"]
-                    .as_array()
-                    .unwrap()
-                    .iter()
-                    .for_each(|object_json| {
-                        if let Some(object) = parse_object(object_json) {
-                            page.add_content_block(object);
-                        }
-                    });
-
-                if !page_json["continuation"].is_null() {
-                    let continuation_content_frame = parse_frame(&page_json["continuation"]["content_frame"]);
-                    page.set_continuation_content_frame(continuation_content_frame);
-
-                    page_json["continuation"]["common_objects"]
-                        .as_array()
-                        .unwrap()
-                        .iter()
-                        .for_each(|object_json| {
-                            if let Some(object) = parse_object(object_json) {
-                                page.add_continuation_common_block(object);
-                            }
-                        });
-                }
-
-                doc.add_page(Page::DynamicPage(page));
-            }
-            _ => {
-                eprintln!("unknown page type");
-            }
-        });
+// NOTE: definitions 内の定義ごとの検証（コンポーネントの展開結果やエラーの詳細の検証に使用）
+fn definition_validator(definition: &str) -> &'static Validator {
+    static VALIDATORS: OnceLock<HashMap<&'static str, Validator>> = OnceLock::new();
+
+    let validators = VALIDATORS.get_or_init(|| {
+        TYPE_DEFINITIONS
+            .iter()
+            .chain(NULLABLE_DEFINITIONS)
+            .map(|(_, definition)| *definition)
+            .chain([ANY_OBJECT_DEFINITION])
+            .map(|definition| {
+                let definition_schema = serde_json::json!({
+                    "definitions": json_schema()["definitions"],
+                    "$ref": definition,
+                });
+                (definition, jsonschema::validator_for(&definition_schema).unwrap())
+            })
+            .collect()
+    });
 
-    doc
+    &validators[definition]
 }
 
-fn parse_page_number(page_number_json: &Value) -> Option<PageNumber> {
-    if page_number_json.is_null() {
-        return None
-    }
-
-    let font_path: Option<String> = page_number_json["font_path"]
-        .as_str()
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string());
+// NOTE: (JSON ポインタ, メッセージ) のリストを返す
+fn schema_errors(validator: &Validator, instance: &Value, pointer: &str) -> Vec<(String, String)> {
+    let mut errors: Vec<(String, String)> = Vec::new();
 
-    let frame = page_number_json["frame"]
-        .as_object()
-        .map(|_| parse_frame(&page_number_json["frame"]));
+    for error in validator.iter_errors(instance) {
+        let error_pointer = format!("{}{}", pointer, error.instance_path);
 
-    let mut page_number = PageNumber::new(
-        page_number_json["format"].as_str()
-            .unwrap_or(PAGE_NUMBER_FORMAT).to_string(),
-        page_number_json["font_size"].as_f64().unwrap() as f32,
-        font_path,
-        frame,
-    );
+        if matches!(error.kind, ValidationErrorKind::AnyOf | ValidationErrorKind::OneOfNotValid) {
+            let refined_errors = refine_schema_errors(error.instance.as_ref(), &error_pointer);
+            if !refined_errors.is_empty() {
+                errors.extend(refined_errors);
+                continue;
+            }
 
-    let style = &page_number_json["style"];
+            // NOTE: 標準のメッセージは値全体を含むため長くなりすぎる
+            errors.push((error_pointer, "is not valid under any of the allowed schemas".to_string()));
+            continue;
+        }
 
-    if style.is_null() {
-        return Some(page_number);
+        errors.push((error_pointer, error.to_string()));
     }
 
-    if !style["text_fill_color"].is_null() {
-        if let Some(text_fill_color) = parse_text_fill_color(&style["text_fill_color"]) {
-            page_number.add_style(text_fill_color);
-        }
+    errors
+}
+
+// NOTE: anyOf / oneOf のどれにも一致しない場合、type（ページ・オブジェクト）や frame の point / size で
+// NOTE: 該当する定義を選んで検証し直し、原因となった値を報告する
+fn refine_schema_errors(instance: &Value, pointer: &str) -> Vec<(String, String)> {
+    if let Some((_, definition)) = instance["type"]
+        .as_str()
+        .and_then(|object_type| TYPE_DEFINITIONS.iter().find(|(name, _)| *name == object_type))
+    {
+        return schema_errors(definition_validator(definition), instance, pointer);
     }
 
-    if !style["text_outline_color"].is_null() {
-        if let Some(text_outline_color) =
-            parse_text_outline_color(&style["text_outline_color"])
-        {
-            page_number.add_style(text_outline_color);
-        }
+    // NOTE: ドキュメントの anyOf は用紙サイズの指定（page_size または width と height）のみ
+    if pointer == "/document" {
+        let missing_keys: Vec<&str> = ["width", "height"]
+            .into_iter()
+            .filter(|key| instance[*key].is_null())
+            .collect();
+
+        return vec![(
+            pointer.to_string(),
+            format!("page_size or width and height is required (missing: page_size, {})", missing_keys.join(", ")),
+        )];
     }
 
-    if !style["text_outline_style"].is_null() {
-        if let Some(text_outline_style) =
-            parse_text_outline_style(&style["text_outline_style"])
-        {
-            page_number.add_style(text_outline_style);
-        }
+    let key = pointer.rsplit('/').next().unwrap_or_default();
+    if key == "page_size"
+        && let Some(name) = instance.as_str()
+    {
+        let names: Vec<&str> = NAMED_PAGE_SIZES.iter().map(|(name, _)| *name).collect();
+        return vec![(
+            pointer.to_string(),
+            format!("Unknown page size: {} (expected one of {})", name, names.join(", ")),
+        )];
     }
 
-    if !style["text_style"].is_null() {
-        if let Some(text_style) = parse_text_style(&style["text_style"]) {
-            page_number.add_style(text_style);
-        }
+    if let Some((_, definition)) = NULLABLE_DEFINITIONS.iter().find(|(name, _)| *name == key)
+        && !instance.is_null()
+    {
+        return schema_errors(definition_validator(definition), instance, pointer);
     }
 
-    Some(page_number)
+    Vec::new()
 }
 
-fn parse_object(object_json: &Value) -> Option<Block> {
-    match object_json["type"].as_str().unwrap() {
-        OBJECT_TYPE_TEXT => {
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
-
-            let font_path: Option<String> = object_json["font_path"]
-                .as_str()
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string());
-
-            let mut text = Text::new(
-                object_json["text"].as_str().unwrap().to_string(),
-                object_json["font_size"].as_f64().unwrap() as f32,
-                font_path,
-                frame,
-            );
+// NOTE: BlockDocument の座標基準は左上（printpdf は左下）
+pub fn parse(json_path: &str, format: Option<InputFormat>, asset_paths: &AssetPaths) -> Document {
+    let (json, source_map) = read_with_source_map(json_path, format);
+    let diagnostics = Diagnostics::new(source_map.as_ref());
 
-            let style = &object_json["style"];
+    let document = parse_with_diagnostics(&json, asset_paths, &diagnostics);
+    diagnostics.exit_if_errors();
 
-            if style.is_null() {
-                return Some(Block::Text(text));
-            }
+    document.unwrap()
+}
 
-            if !style["alignment"].is_null() {
-                if let Some(alignment) = parse_alignment(&style["alignment"]) {
-                    text.add_style(alignment);
-                }
-            }
+// NOTE: YAML / TOML / JSON5 も serde_json::Value に変換して読み込む
+pub fn read(json_path: &str, format: Option<InputFormat>) -> Value {
+    input_format::read(json_path, format)
+}
 
-            if !style["border_color"].is_null() {
-                if let Some(border_color) = parse_border_color(&style["border_color"]) {
-                    text.add_style(border_color);
-                }
-            }
+// NOTE: エラー表示用の SourceMap も返す（行・列の表示は JSON のみ対応）
+pub fn read_with_source_map(json_path: &str, format: Option<InputFormat>) -> (Value, Option<SourceMap>) {
+    let source = input_format::read_source(json_path);
+    let json = input_format::parse_source(json_path, &source, format);
 
-            if !style["border_style"].is_null() {
-                if let Some(border_style) = parse_border_style(&style["border_style"]) {
-                    text.add_style(border_style);
-                }
-            }
+    let source_map = match format.unwrap_or(InputFormat::from_path(json_path)) {
+        InputFormat::Json => {
+            let display_path = if json_path == STDIN_PATH { "<stdin>" } else { json_path };
+            SourceMap::new(display_path, &source)
+        }
+        _ => None,
+    };
 
-            if !style["border_width"].is_null() {
-                if let Some(border_width) = parse_border_width(&style["border_width"]) {
-                    text.add_style(border_width);
-                }
-            }
+    (json, source_map)
+}
 
-            if !style["text_fill_color"].is_null() {
-                if let Some(text_fill_color) = parse_text_fill_color(&style["text_fill_color"]) {
-                    text.add_style(text_fill_color);
-                }
-            }
+// NOTE: テンプレートにデータを差し込んだ後の JSON を解析する場合に使用（エラーの位置は JSON ポインタのみ）
+pub fn parse_value(json: &Value, asset_paths: &AssetPaths) -> Document {
+    let diagnostics = Diagnostics::new(None);
 
-            if !style["text_outline_color"].is_null() {
-                if let Some(text_outline_color) =
-                    parse_text_outline_color(&style["text_outline_color"])
-                {
-                    text.add_style(text_outline_color);
-                }
-            }
+    let document = parse_with_diagnostics(json, asset_paths, &diagnostics);
+    diagnostics.exit_if_errors();
 
-            if !style["text_outline_style"].is_null() {
-                if let Some(text_outline_style) =
-                    parse_text_outline_style(&style["text_outline_style"])
-                {
-                    text.add_style(text_outline_style);
-                }
-            }
+    document.unwrap()
+}
 
-            if !style["text_style"].is_null() {
-                if let Some(text_style) = parse_text_style(&style["text_style"]) {
-                    text.add_style(text_style);
-                }
-            }
+// NOTE: エラーは diagnostics に記録する（エラーがある場合は None）
+// NOTE: テンプレート（コンポーネント・スタイルクラス・継承・相対パス）を展開した JSON を document_serde の型で読み込む
+pub fn parse_with_diagnostics(json: &Value, asset_paths: &AssetPaths, diagnostics: &Diagnostics) -> Option<Document> {
+    let validator = validator();
+    if validator.validate(json).is_err() {
+        for (pointer, message) in schema_errors(validator, json, "") {
+            diagnostics.error(&pointer, message);
+        }
+        return None;
+    }
 
-            if !style["text_wrap"].is_null() {
-                if let Some(text_wrap) = parse_text_wrap(&style["text_wrap"]) {
-                    text.add_style(text_wrap);
-                }
-            }
+    let expanded = expand(json, asset_paths, diagnostics);
+    if diagnostics.has_errors() {
+        return None;
+    }
 
-            Some(Block::Text(text))
+    let mut document = match serde_json::from_value::<Document>(expanded) {
+        Ok(document) => document,
+        Err(e) => {
+            diagnostics.error("/document", e.to_string());
+            return None;
         }
-        OBJECT_TYPE_IMAGE => {
-            let image_path = object_json["path"].as_str().unwrap().to_string();
-
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
+    };
 
-            let mut image = Image::new(image_path, frame);
+    for (page_index, page) in document.pages.iter_mut().enumerate() {
+        set_page_source_pointers(page, &format!("/document/pages/{}", page_index));
+    }
 
-            let style = &object_json["style"];
+    check_margins(&document, json, diagnostics);
+    if diagnostics.has_errors() {
+        return None;
+    }
 
-            if style.is_null() {
-                return Some(Block::Image(image));
-            }
+    Some(document)
+}
 
-            if !style["border_color"].is_null() {
-                if let Some(border_color) = parse_border_color(&style["border_color"]) {
-                    image.add_style(border_color);
-                }
-            }
+// NOTE: 余白でコンテンツ領域がなくなる場合はエラー（dynamic ページは content.frame を省略した場合のみ余白を使用する）
+fn check_margins(document: &Document, json: &Value, diagnostics: &Diagnostics) {
+    let mut reported_pointers: HashSet<String> = HashSet::new();
+
+    for (page_index, page) in document.pages.iter().enumerate() {
+        let (page_size, margin) = match page {
+            Page::StaticPage(page) => (&page.page_size, &page.margin),
+            Page::DynamicPage(page) if json["document"]["pages"][page_index]["content"]["frame"].is_null() => {
+                (&page.page_size, &page.margin)
+            }
+            Page::DynamicPage(_) => continue,
+        };
+
+        let page_size = page_size.as_ref().unwrap_or(&document.page_size);
+        let pointer = match margin {
+            Some(_) => format!("/document/pages/{}/margin", page_index),
+            None => "/document/margin".to_string(),
+        };
+        let margin = margin.as_ref().unwrap_or(&document.margin);
+
+        let is_empty = margin.left + margin.right >= page_size.width || margin.top + margin.bottom >= page_size.height;
+        if is_empty && reported_pointers.insert(pointer.clone()) {
+            diagnostics.error(
+                &pointer,
+                format!(
+                    "margin leaves no content area on a {} x {} mm page",
+                    page_size.width, page_size.height
+                ),
+            );
+        }
+    }
+}
 
-            if !style["border_style"].is_null() {
-                if let Some(border_style) = parse_border_style(&style["border_style"]) {
-                    image.add_style(border_style);
-                }
-            }
+// NOTE: ページ内のオブジェクトの配列（static / dynamic）
+const PAGE_OBJECTS: &[&str] = &[
+    "/objects",
+    "/common/objects",
+    "/content/objects",
+    "/continuation/common_objects",
+    "/first/common_objects",
+    "/last/common_objects",
+    "/odd/common_objects",
+    "/even/common_objects",
+];
+
+// NOTE: styles / components / include を除き、オブジェクトを展開したドキュメント
+fn expand(json: &Value, asset_paths: &AssetPaths, diagnostics: &Diagnostics) -> Value {
+    let mut document = json["document"].clone();
+    let components = load_components(&document, asset_paths, diagnostics);
+    let styles = document["styles"].as_object().cloned().unwrap_or_default();
+    let context = Context::new(&components, &styles, asset_paths, diagnostics);
+
+    let document_map = document.as_object_mut().unwrap();
+    document_map.remove("styles");
+    document_map.remove("components");
+    document_map.remove("include");
+
+    let font_path = asset_paths.resolve(document["font_path"].as_str().unwrap());
+    check_font(&font_path, "/document/font_path", diagnostics);
+    document["font_path"] = Value::from(font_path);
+
+    if document["page_number"].is_object() {
+        let page_number = &mut document["page_number"];
+        let font_path = page_number["font_path"].as_str().filter(|s| !s.is_empty()).map(|s| asset_paths.resolve(s));
+        if let Some(font_path) = font_path.as_ref() {
+            check_font(font_path, "/document/page_number/font_path", diagnostics);
+        }
+        set_or_remove(page_number, "font_path", font_path.map(Value::from));
+    }
 
-            if !style["border_width"].is_null() {
-                if let Some(border_width) = parse_border_width(&style["border_width"]) {
-                    image.add_style(border_width);
-                }
+    for (page_index, page) in document["pages"].as_array_mut().unwrap().iter_mut().enumerate() {
+        let page_pointer = format!("/document/pages/{}", page_index);
+        for objects_pointer in PAGE_OBJECTS {
+            if let Some(objects) = page.pointer_mut(objects_pointer) {
+                *objects = expand_objects(objects, &format!("{}{}", page_pointer, objects_pointer), &context);
             }
-
-            Some(Block::Image(image))
         }
-        OBJECT_TYPE_LINE => {
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
+    }
 
-            let style = &object_json["style"];
+    serde_json::json!({ "document": document })
+}
 
-            let mut line = Line::new(frame.unwrap());
+fn set_or_remove(object: &mut Value, key: &str, value: Option<Value>) {
+    let Some(object) = object.as_object_mut() else {
+        return;
+    };
 
-            if style.is_null() {
-                return Some(Block::Line(line));
-            }
+    match value.filter(|value| !value.is_null()) {
+        Some(value) => object.insert(key.to_string(), value),
+        None => object.remove(key),
+    };
+}
 
-            if !style["space"].is_null() {
-                if let Some(space) = parse_space(&style["space"]) {
-                    (line).add_style(space);
-                }
-            }
+// NOTE: 展開できなかったオブジェクトは null（エラーを記録済み）。配列の位置は入力と同じにする
+fn expand_objects(objects_json: &Value, pointer: &str, context: &Context) -> Value {
+    let objects = objects_json
+        .as_array()
+        .map(|objects| {
+            objects
+                .iter()
+                .enumerate()
+                .map(|(i, object_json)| {
+                    expand_object(object_json, &format!("{}/{}", pointer, i), context).unwrap_or(Value::Null)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Value::Array(objects)
+}
 
-            if !style["border_color"].is_null() {
-                if let Some(border_color) = parse_border_color(&style["border_color"]) {
-                    line.add_style(border_color);
-                }
-            }
+// NOTE: pointer は入力 JSON 上のオブジェクトの位置（エラーの報告に使用）
+fn expand_object(object_json: &Value, pointer: &str, context: &Context) -> Option<Value> {
+    let style = context.resolve_style(object_json, pointer);
+    let object_type = object_json["type"].as_str().unwrap();
 
-            if !style["border_style"].is_null() {
-                if let Some(border_style) = parse_border_style(&style["border_style"]) {
-                    line.add_style(border_style);
-                }
-            }
+    let mut expanded = serde_json::json!({ "type": object_type });
+    match object_type {
+        OBJECT_TYPE_TEXT => {
+            // NOTE: オブジェクトの指定 → style / class → 継承の順に優先する
+            let font_path: Option<String> = object_json["font_path"]
+                .as_str()
+                .or(style.get(STYLE_KEY_FONT_PATH).and_then(|font_path| font_path.as_str()))
+                .filter(|s| !s.is_empty())
+                .map(|s| context.asset_paths.resolve(s));
+            if let Some(font_path) = font_path.as_ref() {
+                check_font(font_path, &field_pointer(object_json, pointer, STYLE_KEY_FONT_PATH), context.diagnostics);
+            }
+
+            let text = object_json["text"].as_str().unwrap();
+            let Some(font_size) = object_json["font_size"]
+                .as_f64()
+                .or(style.get(STYLE_KEY_FONT_SIZE).and_then(|font_size| font_size.as_f64()))
+            else {
+                context.diagnostics.error(pointer, format!("font_size is not specified: {}", text));
+                return None;
+            };
+
+            expanded["text"] = Value::from(text);
+            expanded["font_size"] = Value::from(font_size);
+            set_or_remove(&mut expanded, "font_path", font_path.map(Value::from));
+            set_or_remove(&mut expanded, "frame", Some(object_json["frame"].clone()));
+            set_or_remove(&mut expanded, "style", element_style(&style, TEXT_STYLE_KEYS, pointer, context));
+        }
+        OBJECT_TYPE_IMAGE => {
+            let image_path = context.asset_paths.resolve(object_json["path"].as_str().unwrap());
+            check_image(&image_path, &format!("{}/path", pointer), context.diagnostics);
 
-            if !style["border_width"].is_null() {
-                if let Some(border_width) = parse_border_width(&style["border_width"]) {
-                    line.add_style(border_width);
-                }
+            expanded["path"] = Value::from(image_path);
+            set_or_remove(&mut expanded, "frame", Some(object_json["frame"].clone()));
+            set_or_remove(&mut expanded, "style", element_style(&style, IMAGE_STYLE_KEYS, pointer, context));
+        }
+        OBJECT_TYPE_LINE => {
+            // NOTE: 線は frame.size で始点からの長さを指定する
+            if object_json["frame"]["size"].is_null() {
+                context.diagnostics.error(&format!("{}/frame", pointer), "line requires frame.size");
+                return None;
             }
 
-            Some(Block::Line(line))
+            expanded["frame"] = object_json["frame"].clone();
+            set_or_remove(&mut expanded, "style", element_style(&style, LINE_STYLE_KEYS, pointer, context));
         }
         OBJECT_TYPE_RECTANGLE => {
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
-
-            let mut rectangle = Rectangle::new(frame);
+            set_or_remove(&mut expanded, "frame", Some(object_json["frame"].clone()));
+            set_or_remove(&mut expanded, "style", element_style(&style, RECTANGLE_STYLE_KEYS, pointer, context));
+        }
+        OBJECT_TYPE_COMPONENT => {
+            let name = object_json["name"].as_str().unwrap();
+            let component = context.components.expand(name, object_json, pointer, context.diagnostics)?;
 
-            let style = &object_json["style"];
+            context.components.expanding.borrow_mut().push(name.to_string());
+            let expanded = expand_object(&component, pointer, &context.child(style));
+            context.components.expanding.borrow_mut().pop();
 
-            if style.is_null() {
-                return Some(Block::Rectangle(rectangle));
-            }
+            return expanded;
+        }
+        OBJECT_TYPE_OBJECT => {
+            let object_pointer = format!("{}/object", pointer);
+            expanded["object"] = expand_object(&object_json["object"], &object_pointer, &context.child(style.clone()))?;
+            set_or_remove(&mut expanded, "style", element_style(&style, WRAPPER_STYLE_KEYS, pointer, context));
+        }
+        OBJECT_TYPE_OBJECTS | OBJECT_TYPE_FLEXIBLE => {
+            set_or_remove(&mut expanded, "frame", Some(object_json["frame"].clone()));
+            set_or_remove(&mut expanded, "direction", Some(object_json["direction"].clone()));
+            expanded["objects"] =
+                expand_objects(&object_json["objects"], &format!("{}/objects", pointer), &context.child(style));
+        }
+        OBJECT_TYPE_FLEXIBLE_ITEM => {
+            let object_pointer = format!("{}/object", pointer);
+            expanded["object"] = expand_object(&object_json["object"], &object_pointer, &context.child(style))?;
+            set_or_remove(&mut expanded, "basis", Some(object_json["basis"].clone()));
+        }
+        _ => unreachable!("unknown object type: {}", object_type),
+    }
 
-            if !style["background_color"].is_null() {
-                if let Some(background_color) = parse_background_color(&style["background_color"]) {
-                    rectangle.add_style(background_color);
-                }
-            }
+    Some(expanded)
+}
 
-            if !style["border_color"].is_null() {
-                if let Some(border_color) = parse_border_color(&style["border_color"]) {
-                    rectangle.add_style(border_color);
-                }
-            }
+// NOTE: 要素で使用できるキーのスタイル（null は未指定として扱う）。Style に変換できるかを確認する
+fn element_style(style: &Map<String, Value>, keys: &[&str], pointer: &str, context: &Context) -> Option<Value> {
+    let element_style: Map<String, Value> = keys
+        .iter()
+        .filter_map(|key| style.get(*key).filter(|value| !value.is_null()).map(|value| (key.to_string(), value.clone())))
+        .collect();
+    if element_style.is_empty() {
+        return None;
+    }
 
-            if !style["border_style"].is_null() {
-                if let Some(border_style) = parse_border_style(&style["border_style"]) {
-                    rectangle.add_style(border_style);
-                }
-            }
+    let element_style = Value::Object(element_style);
+    let styles = serde_json::from_value::<StyleClassJson>(element_style.clone())
+        .map_err(|e| e.to_string())
+        .and_then(|style_json| StyleClassJson::into_styles(Some(style_json), keys));
+    if let Err(message) = styles {
+        context.diagnostics.error(pointer, message);
+    }
 
-            if !style["border_width"].is_null() {
-                if let Some(border_width) = parse_border_width(&style["border_width"]) {
-                    rectangle.add_style(border_width);
-                }
-            }
+    Some(element_style)
+}
 
-            Some(Block::Rectangle(rectangle))
+// NOTE: 入力 JSON 上の位置（展開後の JSON の位置と同じ。コンポーネントの場合は参照側の位置）
+fn set_page_source_pointers(page: &mut Page, page_pointer: &str) {
+    let blocks: Vec<(&mut Vec<Block>, &str)> = match page {
+        Page::StaticPage(page) => vec![(&mut page.blocks, "/objects")],
+        Page::DynamicPage(page) => {
+            let mut blocks = vec![
+                (&mut page.common_blocks, "/common/objects"),
+                (&mut page.content_blocks, "/content/objects"),
+                (&mut page.first_common_blocks, "/first/common_objects"),
+                (&mut page.last_common_blocks, "/last/common_objects"),
+                (&mut page.odd_common_blocks, "/odd/common_objects"),
+                (&mut page.even_common_blocks, "/even/common_objects"),
+            ];
+            if let Some(continuation_common_blocks) = page.continuation_common_blocks.as_mut() {
+                blocks.push((continuation_common_blocks, "/continuation/common_objects"));
+            }
+            blocks
         }
-        OBJECT_TYPE_OBJECT => {
-            if let Some(object) = parse_object(&object_json["object"]) {
-                let mut wrapper = Wrapper::new(object);
-
-                let style = &object_json["style"];
-                if !style["space"].is_null() {
-                    if let Some(space) = parse_space(&style["space"]) {
-                        wrapper.add_style(space);
-                    }
-                }
+    };
 
-                return Some(Block::Wrapper(Box::from(wrapper)));
-            }
+    for (blocks, objects_pointer) in blocks {
+        set_source_pointers(blocks, &format!("{}{}", page_pointer, objects_pointer));
+    }
+}
 
-            None
-        }
-        OBJECT_TYPE_OBJECTS => {
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
+fn set_source_pointers(blocks: &mut [Block], pointer: &str) {
+    for (i, block) in blocks.iter_mut().enumerate() {
+        set_source_pointer(block, format!("{}/{}", pointer, i));
+    }
+}
 
-            let mut container = BlockContainer::new(frame);
+fn set_source_pointer(block: &mut Block, pointer: String) {
+    match block {
+        Block::Container(container) => set_source_pointers(&mut container.blocks, &format!("{}/objects", pointer)),
+        Block::Flexible(container) => set_source_pointers(&mut container.blocks, &format!("{}/objects", pointer)),
+        Block::FlexibleItem(item) => set_source_pointer(&mut item.block, format!("{}/object", pointer)),
+        Block::Wrapper(wrapper) => set_source_pointer(&mut wrapper.block, format!("{}/object", pointer)),
+        Block::Text(_) | Block::Line(_) | Block::Rectangle(_) | Block::Image(_) => {}
+    }
 
-            if !object_json["direction"].is_null() {
-                container.set_direction(parse_direction(&object_json["direction"]));
-            }
+    block.set_source_pointer(pointer);
+}
 
-            object_json["objects"]
-                .as_array()
-                .unwrap()
-                .iter()
-                .for_each(|object_json| {
-                    if let Some(object) = parse_object(object_json) {
-                        container.add_block(object);
-                    }
-                });
+// NOTE: オブジェクトの解析に必要な情報（コンポーネント、スタイルクラス、継承されたスタイル）
+struct Context<'a> {
+    components: &'a Components,
+    styles: &'a Map<String, Value>, // NOTE: document.styles
+    inherited: Map<String, Value>,
+    asset_paths: &'a AssetPaths,
+    diagnostics: &'a Diagnostics<'a>,
+}
 
-            Some(Block::Container(container))
+impl<'a> Context<'a> {
+    fn new(
+        components: &'a Components,
+        styles: &'a Map<String, Value>,
+        asset_paths: &'a AssetPaths,
+        diagnostics: &'a Diagnostics<'a>,
+    ) -> Context<'a> {
+        Context {
+            components,
+            styles,
+            inherited: Map::new(),
+            asset_paths,
+            diagnostics,
         }
-        OBJECT_TYPE_FLEXIBLE => {
-            let frame = object_json["frame"]
-                .as_object()
-                .map(|_| parse_frame(&object_json["frame"]));
-
-            let mut container = FlexibleContainer::new(frame);
+    }
 
-            if !object_json["direction"].is_null() {
-                container.set_direction(parse_direction(&object_json["direction"]));
+    // NOTE: 継承されたスタイル → class（指定順）→ style の順に上書きする
+    fn resolve_style(&self, object_json: &Value, pointer: &str) -> Map<String, Value> {
+        let mut style = self.inherited.clone();
+
+        let class_names: Vec<&str> = match &object_json["class"] {
+            Value::String(class_name) => vec![class_name.as_str()],
+            Value::Array(class_names) => class_names.iter().filter_map(|class_name| class_name.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        for class_name in class_names {
+            match self.styles.get(class_name).and_then(|class| class.as_object()) {
+                Some(class) => style.extend(class.clone()),
+                None => self
+                    .diagnostics
+                    .error(&format!("{}/class", pointer), format!("Unknown style class: {}", class_name)),
             }
+        }
 
-            object_json["objects"]
-                .as_array()
-                .unwrap()
-                .iter()
-                .for_each(|object_json| {
-                    if let Some(object) = parse_object(object_json) {
-                        container.add_block(object);
-                    }
-                });
+        if let Some(object_style) = object_json["style"].as_object() {
+            style.extend(object_style.clone());
+        }
+
+        style
+    }
 
-            Some(Block::Flexible(container))
+    // NOTE: 子要素には継承されるスタイルのみを引き継ぐ
+    fn child(&self, style: Map<String, Value>) -> Context<'a> {
+        Context {
+            components: self.components,
+            styles: self.styles,
+            inherited: style
+                .into_iter()
+                .filter(|(key, _)| INHERITED_STYLE_KEYS.contains(&key.as_str()))
+                .collect(),
+            asset_paths: self.asset_paths,
+            diagnostics: self.diagnostics,
         }
-        OBJECT_TYPE_FLEXIBLE_ITEM => {
-            if let Some(object) = parse_object(&object_json["object"]) {
-                let mut basis: Option<f32> = None;
+    }
+}
 
-                if !object_json["basis"].is_null() {
-                    basis = Some(object_json["basis"].as_f64().unwrap() as f32);
-                }
+// NOTE: 名前付きのコンポーネント定義（document.components と include されたファイル）
+struct Components {
+    definitions: HashMap<String, Value>,
+    expanding: RefCell<Vec<String>>, // NOTE: 展開中のコンポーネント（循環参照の検出用）
+}
 
-                return Some(Block::FlexibleItem(Box::from(FlexibleItem::new(
-                    object, basis,
-                ))));
-            }
+impl Components {
+    // NOTE: 引数を差し込んだオブジェクトを返す（frame は参照側の指定を優先）
+    // NOTE: 展開できない場合はエラーを記録して None を返す（pointer は参照側の位置）
+    fn expand(&self, name: &str, object_json: &Value, pointer: &str, diagnostics: &Diagnostics) -> Option<Value> {
+        let Some(definition) = self.definitions.get(name) else {
+            let mut names: Vec<&str> = self.definitions.keys().map(|name| name.as_str()).collect();
+            names.sort();
+            let available = if names.is_empty() {
+                "no components are defined".to_string()
+            } else {
+                format!("available components: {}", names.join(", "))
+            };
+            diagnostics.error(&format!("{}/name", pointer), format!("Unknown component: {} ({})", name, available));
+            return None;
+        };
+
+        if self.expanding.borrow().iter().any(|expanding| expanding == name) {
+            let mut cycle = self.expanding.borrow().clone();
+            cycle.push(name.to_string());
+            diagnostics.error(pointer, format!("Component cycle detected: {}", cycle.join(" -> ")));
+            return None;
+        }
 
-            None
+        let mut params = definition["params"].as_object().cloned().unwrap_or_default();
+        if let Some(object_params) = object_json["params"].as_object() {
+            params.extend(object_params.clone());
         }
-        _ => {
-            eprintln!("unknown block type");
 
-            None
+        let mut expanded = template::bind(&definition["object"], &Value::Object(params));
+        // NOTE: 定義の object の if / unless を満たさない場合は空のコンテナにする（配列内の位置を変えないため）
+        if expanded.is_null() {
+            return Some(serde_json::json!({ "type": OBJECT_TYPE_OBJECTS, "objects": [] }));
+        }
+        if object_json["frame"].is_object() {
+            expanded["frame"] = object_json["frame"].clone();
+        }
+
+        // NOTE: 展開後の位置は入力ファイルにないため、コンポーネント内の JSON ポインタをメッセージに含める
+        let errors = schema_errors(definition_validator(ANY_OBJECT_DEFINITION), &expanded, "");
+        if !errors.is_empty() {
+            for (error_pointer, message) in errors {
+                let error_pointer = if error_pointer.is_empty() { "/" } else { error_pointer.as_str() };
+                diagnostics.error(pointer, format!("Invalid component {} at {}: {}", name, error_pointer, message));
+            }
+            return None;
         }
+
+        Some(expanded)
     }
 }
 
-fn parse_frame(frame_json: &Value) -> GeoRect {
-    match frame_json.as_object() {
-        Some(frame) => {
-            let point_x = frame["point"]["x"].as_f64();
-            let point_y = frame["point"]["y"].as_f64();
-            let size_w = frame["size"]["width"].as_f64();
-            let size_h = frame["size"]["height"].as_f64();
-
-            match (point_x, point_y, size_w, size_h) {
-                (Some(x), Some(y), Some(w), Some(h)) => {
-                    GeoRect::new(w as f32, h as f32, x as f32, y as f32)
-                }
-                (Some(x), Some(y), None, None) => GeoRect {
-                    point: Some(GeoPoint {
-                        x: x as f32,
-                        y: y as f32,
-                    }),
-                    size: None,
-                },
-                (None, None, Some(w), Some(h)) => GeoRect {
-                    point: None,
-                    size: Some(GeoSize {
-                        width: w as f32,
-                        height: h as f32,
-                    }),
-                },
-                _ => GeoRect::none(),
+fn load_components(document_json: &Value, asset_paths: &AssetPaths, diagnostics: &Diagnostics) -> Components {
+    let mut definitions: HashMap<String, Value> = HashMap::new();
+    let mut including: Vec<PathBuf> = Vec::new();
+
+    if let Some(paths) = document_json["include"].as_array() {
+        for (i, path) in paths.iter().enumerate() {
+            let pointer = format!("/document/include/{}", i);
+            if let Err(message) = include_components(path, asset_paths, &mut definitions, &mut including) {
+                diagnostics.error(&pointer, message);
             }
         }
-        _ => GeoRect::none(),
     }
-}
 
-fn parse_direction(direction_json: &Value) -> Direction {
-    match direction_json.as_str().unwrap() {
-        "horizontal" => Direction::Horizontal,
-        "vertical" => Direction::Vertical,
-        _ => {
-            panic!("unknown direction");
+    // NOTE: ドキュメントの定義は include された定義より優先する
+    if let Some(components) = document_json["components"].as_object() {
+        for (name, definition) in components.iter() {
+            definitions.insert(name.clone(), definition.clone());
         }
     }
-}
 
-fn parse_background_color(background_color_json: &Value) -> Option<Style> {
-    Some(Style::BackgroundColor(RgbColor {
-        r: background_color_json["red"].as_u64().unwrap() as u8,
-        g: background_color_json["green"].as_u64().unwrap() as u8,
-        b: background_color_json["blue"].as_u64().unwrap() as u8,
-    }))
+    Components {
+        definitions,
+        expanding: RefCell::new(Vec::new()),
+    }
 }
 
-fn parse_border_color(border_color_json: &Value) -> Option<Style> {
-    Some(Style::BorderColor(RgbColor {
-        r: border_color_json["red"].as_u64().unwrap() as u8,
-        g: border_color_json["green"].as_u64().unwrap() as u8,
-        b: border_color_json["blue"].as_u64().unwrap() as u8,
-    }))
-}
+// NOTE: include されたファイルの components を読み込む（ファイル内の include はそのファイルからの相対パス）
+// NOTE: エラーは include したドキュメント側の位置で報告する（サーバーモードで終了しないように exit しない）
+fn include_components(
+    path_json: &Value,
+    asset_paths: &AssetPaths,
+    definitions: &mut HashMap<String, Value>,
+    including: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    let Some(path) = path_json.as_str() else {
+        return Ok(());
+    };
 
-fn parse_border_style(border_json: &Value) -> Option<Style> {
-    match border_json["line_style"].as_str().unwrap() {
-        "solid" => Some(Style::BorderStyle(BorderStyle::Solid)),
-        "dash" => {
-            let dash_1 = border_json["dash_1"].as_i64().unwrap();
-            Some(Style::BorderStyle(BorderStyle::Dash(dash_1)))
-        }
-        _ => None,
+    let resolved_path = asset_paths.resolve(path);
+    let canonical_path = Path::new(&resolved_path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to include {} ({}): {}", path, resolved_path, e))?;
+
+    if including.contains(&canonical_path) {
+        let cycle: Vec<String> = including
+            .iter()
+            .chain([&canonical_path])
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(format!("Include cycle detected: {}", cycle.join(" -> ")));
     }
-}
 
-fn parse_border_width(border_width_json: &Value) -> Option<Style> {
-    Some(Style::BorderWidth(
-        border_width_json["width"].as_f64().unwrap() as f32,
-    ))
-}
-
-fn parse_alignment(alignment_json: &Value) -> Option<Style> {
-    Some(Style::Alignment(Alignment {
-        horizontal: match alignment_json["horizontal"].as_str().unwrap_or("") {
-            "left" => Some(HorizontalAlignment::Left),
-            "center" => Some(HorizontalAlignment::Center),
-            "right" => Some(HorizontalAlignment::Right),
-            _ => None,
-        },
-        vertical: match alignment_json["vertical"].as_str().unwrap_or("") {
-            "top" => Some(VerticalAlignment::Top),
-            "center" => Some(VerticalAlignment::Center),
-            "bottom" => Some(VerticalAlignment::Bottom),
-            _ => None,
-        },
-    }))
-}
+    let canonical_path_string = canonical_path.to_string_lossy().to_string();
+    let json = std::fs::read_to_string(&canonical_path)
+        .map_err(|e| format!("Failed to read {}: {}", canonical_path_string, e))
+        .and_then(|source| {
+            let format = InputFormat::from_path(&canonical_path_string);
+            format
+                .parse(&source)
+                .map_err(|e| format!("Failed to parse {} as {:?}: {}", canonical_path_string, format, e))
+        })?;
+
+    let include_asset_paths = AssetPaths::new(canonical_path.parent().unwrap().to_path_buf());
+    including.push(canonical_path);
+    let result = json["include"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .try_for_each(|path| include_components(path, &include_asset_paths, definitions, including));
+    including.pop();
+    result?;
+
+    if let Some(components) = json["components"].as_object() {
+        for (name, definition) in components.iter() {
+            definitions.insert(name.clone(), definition.clone());
+        }
+    }
 
-fn parse_space(space_json: &Value) -> Option<Style> {
-    Some(Style::Space(Space {
-        top: space_json["top"].as_f64().unwrap() as f32,
-        right: space_json["right"].as_f64().unwrap() as f32,
-        bottom: space_json["bottom"].as_f64().unwrap() as f32,
-        left: space_json["left"].as_f64().unwrap() as f32,
-    }))
+    Ok(())
 }
 
-fn parse_text_fill_color(text_fill_color_json: &Value) -> Option<Style> {
-    Some(Style::TextFillColor(RgbColor {
-        r: text_fill_color_json["red"].as_u64().unwrap() as u8,
-        g: text_fill_color_json["green"].as_u64().unwrap() as u8,
-        b: text_fill_color_json["blue"].as_u64().unwrap() as u8,
-    }))
-}
+// NOTE: オブジェクトに直接指定されていない場合（style / class / 継承）はオブジェクトの位置
+fn field_pointer(object_json: &Value, pointer: &str, key: &str) -> String {
+    if object_json[key].is_null() {
+        return pointer.to_string();
+    }
 
-fn parse_text_outline_color(text_outline_color: &Value) -> Option<Style> {
-    Some(Style::TextOutlineColor(RgbColor {
-        r: text_outline_color["red"].as_u64().unwrap() as u8,
-        g: text_outline_color["green"].as_u64().unwrap() as u8,
-        b: text_outline_color["blue"].as_u64().unwrap() as u8,
-    }))
+    format!("{}/{}", pointer, key)
 }
 
-fn parse_text_outline_style(text_outline_style_json: &Value) -> Option<Style> {
-    match text_outline_style_json["line_style"].as_str().unwrap() {
-        "solid" => Some(Style::TextOutlineStyle(TextOutlineStyle::Solid)),
-        "dash" => {
-            let dash_1 = text_outline_style_json["dash_1"].as_i64().unwrap();
-            Some(Style::TextOutlineStyle(TextOutlineStyle::Dash(dash_1)))
-        }
-        _ => None,
+// NOTE: 描画の前にフォントを読み込めるか確認する
+fn check_font(font_path: &str, pointer: &str, diagnostics: &Diagnostics) {
+    if load_font(font_path).is_some() {
+        return;
     }
-}
 
-fn parse_text_style(text_style_json: &Value) -> Option<Style> {
-    let line_style = text_style_json["line_style"].as_str().unwrap();
-    match line_style {
-        "fill" => Some(Style::TextStyle(TextStyle::Fill)),
-        "stroke" => Some(Style::TextStyle(TextStyle::Stroke)),
-        "fill_stroke" => Some(Style::TextStyle(TextStyle::FillStroke)),
-        _ => None,
+    if !data_uri::is_data_uri(font_path) && !Path::new(font_path).exists() {
+        diagnostics.error(pointer, format!("Font file not found: {}", font_path));
+    } else {
+        diagnostics.error(pointer, format!("Failed to load font: {}", data_uri::display(font_path)));
     }
 }
 
-fn parse_text_wrap(text_wrap_json: &Value) -> Option<Style> {
-    let mode = match text_wrap_json["mode"].as_str().unwrap() {
-        "none" => TextWrapMode::None,
-        "word" => TextWrapMode::Word,
-        "character" => TextWrapMode::Character,
-        _ => return None,
+// NOTE: 描画の前に画像を読み込めるか確認する
+fn check_image(path: &str, pointer: &str, diagnostics: &Diagnostics) {
+    let Err(e) = load_image(path) else {
+        return;
     };
 
-    let break_anywhere = text_wrap_json["break_anywhere"].as_bool().unwrap_or(false);
-
-    let overflow = match text_wrap_json["overflow"].as_str().unwrap_or("clip") {
-        "clip" => TextOverflow::Clip,
-        "ellipsis" => TextOverflow::Ellipsis,
-        _ => TextOverflow::Clip,
-    };
-
-    Some(Style::TextWrap(TextWrap {
-        mode,
-        break_anywhere,
-        overflow,
-    }))
+    if !data_uri::is_data_uri(path) && !Path::new(path).exists() {
+        diagnostics.error(pointer, format!("Image file not found: {}", path));
+    } else {
+        diagnostics.error(pointer, format!("Failed to load image: {} ({})", data_uri::display(path), e));
+    }
 }
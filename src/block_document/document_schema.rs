@@ -0,0 +1,239 @@
+use crate::block_document::block::Block;
+use crate::block_document::document::Document;
+use crate::block_document::document_json::{
+    IMAGE_STYLE_KEYS, INHERITED_STYLE_KEYS, LINE_STYLE_KEYS, PAGE_NUMBER_STYLE_KEYS, RECTANGLE_STYLE_KEYS,
+    TEXT_STYLE_KEYS, WRAPPER_STYLE_KEYS,
+};
+use crate::block_document::document_serde::{
+    BlockJson, ComponentJson, DynamicPageJson, FlexibleItemJson, FlexibleJson, ImageJson, LineJson, ObjectJson,
+    ObjectsJson, PageJson, RectangleJson, StaticPageJson, StyleClassJson, TextJson,
+};
+use crate::block_document::page_size::NAMED_PAGE_SIZES;
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde_json::Value;
+use std::borrow::Cow;
+
+type SubschemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+// NOTE: ページとコンテナ（objects）に配置できるオブジェクト（flexible_item は flexible の中のみ）
+const CONTAINER_OBJECTS: &[SubschemaFn] = &[
+    subschema::<ObjectJson>,
+    subschema::<TextJson>,
+    subschema::<ImageJson>,
+    subschema::<LineJson>,
+    subschema::<RectangleJson>,
+    subschema::<ObjectsJson>,
+    subschema::<FlexibleJson>,
+    subschema::<ComponentJson>,
+];
+const FLEXIBLE_OBJECTS: &[SubschemaFn] = &[
+    subschema::<TextJson>,
+    subschema::<ImageJson>,
+    subschema::<LineJson>,
+    subschema::<RectangleJson>,
+    subschema::<ObjectsJson>,
+    subschema::<FlexibleJson>,
+    subschema::<ComponentJson>,
+    subschema::<FlexibleItemJson>,
+];
+const WRAPPED_OBJECTS: &[SubschemaFn] = &[subschema::<TextJson>, subschema::<ImageJson>, subschema::<RectangleJson>];
+const ANY_OBJECTS: &[SubschemaFn] = &[
+    subschema::<TextJson>,
+    subschema::<ImageJson>,
+    subschema::<LineJson>,
+    subschema::<RectangleJson>,
+    subschema::<ObjectJson>,
+    subschema::<ObjectsJson>,
+    subschema::<FlexibleJson>,
+    subschema::<FlexibleItemJson>,
+    subschema::<ComponentJson>,
+];
+const PAGES: &[SubschemaFn] = &[subschema::<StaticPageJson>, subschema::<DynamicPageJson>];
+
+// NOTE: レイアウトファイルの JSON スキーマ（schema/schema.json は `rs-pdf schema` で生成する）
+pub fn generate() -> Value {
+    let mut generator = SchemaSettings::draft07().into_generator();
+    // NOTE: コンポーネントの展開結果の検証に使用する（document_json）
+    generator.subschema_for::<Block>();
+
+    let mut schema = generator.root_schema_for::<Document>().to_value();
+    remove_optional_null(&mut schema);
+    schema
+}
+
+// NOTE: Option のフィールドは null も許可するスキーマになるが、省略できるという意味でのみ使用するため null を除く
+// NOTE: （anyOf のエラーは原因の値を報告できないため）。null を指定できる値は nullable にする
+fn remove_optional_null(value: &mut Value) {
+    let Value::Object(object) = value else {
+        if let Value::Array(array) = value {
+            array.iter_mut().for_each(remove_optional_null);
+        }
+        return;
+    };
+
+    if let Some(Value::Array(types)) = object.get_mut("type") {
+        types.retain(|schema_type| schema_type != "null");
+        if let [schema_type] = types.as_slice() {
+            let schema_type = schema_type.clone();
+            object.insert("type".to_string(), schema_type);
+        }
+    }
+
+    let optional = match object.get("anyOf").and_then(Value::as_array).map(Vec::as_slice) {
+        Some([schema, null]) if object.len() == 1 && null["type"] == "null" => Some(schema.clone()),
+        _ => None,
+    };
+    match optional {
+        Some(optional) => {
+            *value = optional;
+            remove_optional_null(value);
+        }
+        None => object.values_mut().for_each(remove_optional_null),
+    }
+}
+
+fn subschema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+fn any_of(generator: &mut SchemaGenerator, subschemas: &[SubschemaFn]) -> Schema {
+    let subschemas: Vec<Schema> = subschemas.iter().map(|subschema| subschema(generator)).collect();
+    json_schema!({ "anyOf": subschemas })
+}
+
+fn objects(generator: &mut SchemaGenerator, subschemas: &[SubschemaFn]) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": any_of(generator, subschemas),
+    })
+}
+
+// NOTE: ページ・オブジェクトの type（internally tagged の enum のタグ）
+pub fn object_type(name: &'static str) -> impl FnMut(&mut Schema) {
+    move |schema: &mut Schema| {
+        let object = schema.ensure_object();
+        if let Some(Value::Object(properties)) = object.get_mut("properties") {
+            let mut typed_properties = serde_json::Map::new();
+            typed_properties.insert("type".to_string(), serde_json::json!({ "type": "string", "const": name }));
+            typed_properties.append(properties);
+            *properties = typed_properties;
+        }
+
+        let mut required = vec![Value::from("type")];
+        if let Some(Value::Array(object_required)) = object.remove("required") {
+            required.extend(object_required);
+        }
+        object.insert("required".to_string(), Value::from(required));
+    }
+}
+
+// NOTE: null を指定できる値（frame の point / size）
+pub fn nullable<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "oneOf": [generator.subschema_for::<T>(), { "type": "null" }],
+    })
+}
+
+pub fn page_size_name(_: &mut SchemaGenerator) -> Schema {
+    let names: Vec<&str> = NAMED_PAGE_SIZES.iter().map(|(name, _)| *name).collect();
+    json_schema!({
+        "type": "string",
+        "enum": names,
+    })
+}
+
+pub fn container_objects(generator: &mut SchemaGenerator) -> Schema {
+    objects(generator, CONTAINER_OBJECTS)
+}
+
+pub fn flexible_objects(generator: &mut SchemaGenerator) -> Schema {
+    objects(generator, FLEXIBLE_OBJECTS)
+}
+
+pub fn wrapped_object(generator: &mut SchemaGenerator) -> Schema {
+    any_of(generator, WRAPPED_OBJECTS)
+}
+
+pub fn flexible_item_object(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<TextJson>()
+}
+
+pub fn component_object(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "object",
+        "description": "{{引数名}} は配置時の params で置き換えられる",
+        "properties": {
+            "type": { "type": "string" },
+        },
+        "required": ["type"],
+    })
+}
+
+// NOTE: 要素ごとの "style"（スタイルクラスのうち、その要素で使用できるキーのみ）
+fn style(generator: &mut SchemaGenerator, keys: &[&str]) -> Schema {
+    let mut schema = StyleClassJson::json_schema(generator);
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        properties.retain(|key, _| keys.contains(&key.as_str()));
+    }
+    schema
+}
+
+pub fn text_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, TEXT_STYLE_KEYS)
+}
+
+pub fn image_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, IMAGE_STYLE_KEYS)
+}
+
+pub fn line_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, LINE_STYLE_KEYS)
+}
+
+pub fn rectangle_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, RECTANGLE_STYLE_KEYS)
+}
+
+pub fn wrapper_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, WRAPPER_STYLE_KEYS)
+}
+
+pub fn page_number_style(generator: &mut SchemaGenerator) -> Schema {
+    style(generator, PAGE_NUMBER_STYLE_KEYS)
+}
+
+pub fn inherited_style(generator: &mut SchemaGenerator) -> Schema {
+    let mut schema = style(generator, INHERITED_STYLE_KEYS);
+    schema.insert("description".to_string(), Value::from("子要素のテキストに継承されるスタイル"));
+    schema
+}
+
+// NOTE: type で区別する enum は serde の形式（internally tagged）の代わりに type ごとの定義の anyOf にする
+impl JsonSchema for PageJson {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "PageJson".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        any_of(generator, PAGES)
+    }
+}
+
+impl JsonSchema for BlockJson {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "BlockJson".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        any_of(generator, ANY_OBJECTS)
+    }
+}
@@ -0,0 +1,1008 @@
+use crate::block_document::block::Block;
+use crate::block_document::block_container::BlockContainer;
+use crate::block_document::direction::Direction;
+use crate::block_document::document::Document;
+use crate::block_document::document_json::{
+    IMAGE_STYLE_KEYS, LINE_STYLE_KEYS, PAGE_NUMBER_STYLE_KEYS, RECTANGLE_STYLE_KEYS, STYLE_KEY_ALIGNMENT,
+    STYLE_KEY_BACKGROUND_COLOR, STYLE_KEY_BORDER_COLOR, STYLE_KEY_BORDER_STYLE, STYLE_KEY_BORDER_WIDTH,
+    STYLE_KEY_SPACE, STYLE_KEY_TEXT_FILL_COLOR, STYLE_KEY_TEXT_OUTLINE_COLOR, STYLE_KEY_TEXT_OUTLINE_STYLE,
+    STYLE_KEY_TEXT_STYLE, STYLE_KEY_TEXT_WRAP, TEXT_STYLE_KEYS, WRAPPER_STYLE_KEYS,
+};
+use crate::block_document::document_schema::{self, object_type};
+use crate::block_document::dynamic_page::DynamicPage;
+use crate::block_document::flexible_container::FlexibleContainer;
+use crate::block_document::flexible_item::FlexibleItem;
+use crate::block_document::geometry::{GeoRect, GeoSize};
+use crate::block_document::image::Image;
+use crate::block_document::line::Line;
+use crate::block_document::page::Page;
+use crate::block_document::page_number::{self, NumberStyle, PageNumber, PageNumbering};
+use crate::block_document::page_size::{self, named_page_size, Orientation};
+use crate::block_document::rectangle::Rectangle;
+use crate::block_document::static_page::StaticPage;
+use crate::block_document::style::{
+    Alignment, BorderStyle, RgbColor, Space, Style, TextOutlineStyle, TextStyle, TextWrap,
+};
+use crate::block_document::text::Text;
+use crate::block_document::wrapper::Wrapper;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+// NOTE: schema.json の形式と Document の相互変換（serde）。schema.json もこの型から生成する（document_schema）
+// NOTE: 出力はコンポーネントやクラスを展開した形式のため、読み込みも展開済みの形式のみに対応する
+// NOTE: （components / styles / include / class などのテンプレートは document_json で展開してからこの型で読み込む）
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct DocumentJson {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub document: DocumentBodyJson,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline, extend("anyOf" = [{ "required": ["width", "height"] }, { "required": ["page_size"] }]))]
+pub struct DocumentBodyJson {
+    #[schemars(length(min = 1))]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1.0))]
+    pub width: Option<f32>, // NOTE: mm
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1.0))]
+    pub height: Option<f32>, // NOTE: mm
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<PageSizeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<Orientation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<Space>,
+    #[schemars(length(min = 1))]
+    pub font_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "名前付きのスタイルクラス")]
+    pub styles: Option<BTreeMap<String, StyleClassJson>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<BTreeMap<String, ComponentDefinitionJson>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(inner(length(min = 1)), description = "components を定義したJSONファイルのパス")]
+    pub include: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<PageNumber>,
+    pub pages: Vec<PageJson>,
+}
+
+// NOTE: 用紙サイズの名前（A4 など）または幅と高さ
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(rename = "page_size")]
+pub enum PageSizeJson {
+    Named(#[schemars(schema_with = "document_schema::page_size_name")] String),
+    Size(GeoSize),
+}
+
+impl PageSizeJson {
+    fn resolve(self) -> Result<GeoSize, String> {
+        match self {
+            PageSizeJson::Named(name) => named_page_size(&name).ok_or(format!("Unknown page size: {}", name)),
+            PageSizeJson::Size(size) => Ok(size),
+        }
+    }
+}
+
+// NOTE: テンプレートの機能は document_json でのみ展開する
+fn template_error(key: &str) -> String {
+    format!("{} is not supported; parse templates with document_json", key)
+}
+
+impl TryFrom<DocumentJson> for Document {
+    type Error = String;
+
+    fn try_from(json: DocumentJson) -> Result<Document, String> {
+        let body = json.document;
+        if body.styles.is_some() {
+            return Err(template_error("styles"));
+        }
+        if body.components.is_some() {
+            return Err(template_error("components"));
+        }
+        if body.include.is_some() {
+            return Err(template_error("include"));
+        }
+
+        // NOTE: page_size が指定されていない場合は width / height を使用
+        let document_size = GeoSize::new(body.width.unwrap_or_default(), body.height.unwrap_or_default());
+        let page_size = body.page_size.map(PageSizeJson::resolve).transpose()?;
+
+        let mut document = Document::new(
+            body.title,
+            page_size::resolve(page_size, body.orientation, &document_size).unwrap_or(document_size),
+            body.font_path,
+        );
+        if let Some(margin) = body.margin {
+            document.set_margin(margin);
+        }
+        if let Some(page_number) = body.page_number {
+            document.set_page_number(page_number);
+        }
+
+        for page in body.pages {
+            let page = page.into_page(Some((&document.page_size, &document.margin)))?;
+            document.add_page(page);
+        }
+
+        Ok(document)
+    }
+}
+
+impl From<Document> for DocumentJson {
+    fn from(document: Document) -> DocumentJson {
+        DocumentJson {
+            schema: None,
+            document: DocumentBodyJson {
+                title: document.title,
+                width: Some(document.page_size.width),
+                height: Some(document.page_size.height),
+                page_size: None,
+                orientation: None,
+                margin: Some(document.margin),
+                font_path: document.font_path,
+                styles: None,
+                components: None,
+                include: None,
+                page_number: document.page_number,
+                pages: document.pages.into_iter().map(PageJson::from).collect(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct PageNumberJson {
+    #[serde(default = "default_page_number_format")]
+    #[schemars(
+        description = "Placeholders: ${CURRENT_PAGE_NUMBER}, ${TOTAL_PAGES}, ${SECTION_PAGE}, ${SECTION_PAGES}"
+    )]
+    pub format: String,
+    #[serde(default)]
+    pub number_style: NumberStyle,
+    #[schemars(range(min = 1.0))]
+    pub font_size: f32, // NOTE: PT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::page_number_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+fn default_page_number_format() -> String {
+    page_number::DEFAULT_FORMAT.to_string()
+}
+
+impl TryFrom<PageNumberJson> for PageNumber {
+    type Error = String;
+
+    fn try_from(json: PageNumberJson) -> Result<PageNumber, String> {
+        let mut page_number = PageNumber::new(json.format, json.font_size, json.font_path, json.frame);
+        page_number.set_number_style(json.number_style);
+        StyleClassJson::into_styles(json.style, PAGE_NUMBER_STYLE_KEYS)?
+            .into_iter()
+            .for_each(|style| page_number.add_style(style));
+        Ok(page_number)
+    }
+}
+
+impl From<PageNumber> for PageNumberJson {
+    fn from(page_number: PageNumber) -> PageNumberJson {
+        PageNumberJson {
+            format: page_number.format,
+            number_style: page_number.number_style,
+            font_size: page_number.font_size,
+            font_path: page_number.font_path,
+            frame: page_number.frame,
+            style: StyleClassJson::from_styles(page_number.styles),
+        }
+    }
+}
+
+// NOTE: スキーマは type ごとの定義の anyOf（document_schema）
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PageJson {
+    Static(StaticPageJson),
+    Dynamic(Box<DynamicPageJson>),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "static_page", transform = object_type("static"))]
+pub struct StaticPageJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<Space>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<PageSizeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<Orientation>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    #[schemars(description = "Automatically paginate blocks that don't fit on the current page")]
+    pub auto_pagination: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_numbering: Option<PageNumbering>,
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub objects: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "dynamic_page", transform = object_type("dynamic"))]
+pub struct DynamicPageJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<Space>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<PageSizeJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<Orientation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_numbering: Option<PageNumbering>,
+    pub common: CommonJson,
+    pub content: ContentJson,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<ContinuationJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "最初のページのみに表示される共通要素")]
+    pub first: Option<CommonObjectsJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "最後のページのみに表示される共通要素")]
+    pub last: Option<CommonObjectsJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "奇数ページに表示される共通要素とコンテンツ領域")]
+    pub odd: Option<AlternateJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "偶数ページに表示される共通要素とコンテンツ領域")]
+    pub even: Option<AlternateJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct CommonJson {
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub objects: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct ContentJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>, // NOTE: 未指定の場合は余白を除いたページ全体
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub objects: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct ContinuationJson {
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub common_objects: Vec<Block>,
+    pub content_frame: GeoRect,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct CommonObjectsJson {
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub common_objects: Vec<Block>,
+}
+
+// NOTE: 奇数・偶数ページ
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
+pub struct AlternateJson {
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub common_objects: Vec<Block>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_frame: Option<GeoRect>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl PageJson {
+    // NOTE: document はドキュメントの用紙サイズと余白（orientation のみの指定や content.frame の省略に使用する）
+    fn into_page(self, document: Option<(&GeoSize, &Space)>) -> Result<Page, String> {
+        match self {
+            PageJson::Static(json) => {
+                let mut page = StaticPage::new();
+                page.set_auto_pagination(json.auto_pagination);
+                page.set_page_numbering(json.page_numbering.unwrap_or_default());
+                if let Some(page_size) = resolve_page_size(json.page_size, json.orientation, document)? {
+                    page.set_page_size(page_size);
+                }
+                if let Some(margin) = json.margin {
+                    page.set_margin(margin);
+                }
+                json.objects.into_iter().for_each(|block| page.add_block(block));
+
+                Ok(Page::StaticPage(page))
+            }
+            PageJson::Dynamic(json) => {
+                let mut page = DynamicPage::new();
+                page.set_page_numbering(json.page_numbering.unwrap_or_default());
+                if let Some(page_size) = resolve_page_size(json.page_size, json.orientation, document)? {
+                    page.set_page_size(page_size);
+                }
+                if let Some(margin) = json.margin {
+                    page.set_margin(margin);
+                }
+
+                let content_frame = match (json.content.frame, document) {
+                    (Some(frame), _) => frame,
+                    (None, Some((document_size, document_margin))) => {
+                        let page_size = page.page_size.clone().unwrap_or(document_size.clone());
+                        GeoRect::new(page_size.width, page_size.height, 0.0, 0.0)
+                            .inset(page.margin.as_ref().unwrap_or(document_margin))
+                    }
+                    (None, None) => return Err("content.frame is required outside a document".to_string()),
+                };
+                page.set_content_frame(content_frame);
+
+                json.common.objects.into_iter().for_each(|block| page.add_common_block(block));
+                json.content.objects.into_iter().for_each(|block| page.add_content_block(block));
+
+                if let Some(continuation) = json.continuation {
+                    page.set_continuation_content_frame(continuation.content_frame);
+                    continuation
+                        .common_objects
+                        .into_iter()
+                        .for_each(|block| page.add_continuation_common_block(block));
+                }
+                if let Some(first) = json.first {
+                    first.common_objects.into_iter().for_each(|block| page.add_first_common_block(block));
+                }
+                if let Some(last) = json.last {
+                    last.common_objects.into_iter().for_each(|block| page.add_last_common_block(block));
+                }
+                if let Some(odd) = json.odd {
+                    odd.common_objects.into_iter().for_each(|block| page.add_odd_common_block(block));
+                    if let Some(content_frame) = odd.content_frame {
+                        page.set_odd_content_frame(content_frame);
+                    }
+                }
+                if let Some(even) = json.even {
+                    even.common_objects.into_iter().for_each(|block| page.add_even_common_block(block));
+                    if let Some(content_frame) = even.content_frame {
+                        page.set_even_content_frame(content_frame);
+                    }
+                }
+
+                Ok(Page::DynamicPage(Box::new(page)))
+            }
+        }
+    }
+}
+
+fn resolve_page_size(
+    page_size: Option<PageSizeJson>,
+    orientation: Option<Orientation>,
+    document: Option<(&GeoSize, &Space)>,
+) -> Result<Option<GeoSize>, String> {
+    let page_size = page_size.map(PageSizeJson::resolve).transpose()?;
+    match (page_size, orientation, document) {
+        (None, Some(_), None) => Err("orientation without page_size is only allowed inside a document".to_string()),
+        (page_size, orientation, document) => {
+            let default_page_size = document.map(|(page_size, _)| page_size.clone()).unwrap_or_default();
+            Ok(page_size::resolve(page_size, orientation, &default_page_size))
+        }
+    }
+}
+
+impl TryFrom<PageJson> for Page {
+    type Error = String;
+
+    fn try_from(json: PageJson) -> Result<Page, String> {
+        json.into_page(None)
+    }
+}
+
+impl From<Page> for PageJson {
+    fn from(page: Page) -> PageJson {
+        match page {
+            Page::StaticPage(page) => PageJson::Static(StaticPageJson {
+                margin: page.margin,
+                page_size: page.page_size.map(PageSizeJson::Size),
+                orientation: None,
+                auto_pagination: page.auto_pagination,
+                page_numbering: Some(page.page_numbering),
+                objects: page.blocks,
+            }),
+            Page::DynamicPage(page) => {
+                let continuation = match (page.continuation_content_frame, page.continuation_common_blocks) {
+                    (None, None) => None,
+                    (content_frame, common_blocks) => Some(ContinuationJson {
+                        common_objects: common_blocks.unwrap_or_default(),
+                        content_frame: content_frame.unwrap_or(GeoRect::none()),
+                    }),
+                };
+
+                PageJson::Dynamic(Box::from(DynamicPageJson {
+                    margin: page.margin,
+                    page_size: page.page_size.map(PageSizeJson::Size),
+                    orientation: None,
+                    page_numbering: Some(page.page_numbering),
+                    common: CommonJson { objects: page.common_blocks },
+                    content: ContentJson {
+                        frame: Some(page.content_frame),
+                        objects: page.content_blocks,
+                    },
+                    continuation,
+                    first: common_objects(page.first_common_blocks),
+                    last: common_objects(page.last_common_blocks),
+                    odd: alternate(page.odd_common_blocks, page.odd_content_frame),
+                    even: alternate(page.even_common_blocks, page.even_content_frame),
+                }))
+            }
+        }
+    }
+}
+
+fn common_objects(blocks: Vec<Block>) -> Option<CommonObjectsJson> {
+    if blocks.is_empty() {
+        return None;
+    }
+
+    Some(CommonObjectsJson { common_objects: blocks })
+}
+
+fn alternate(blocks: Vec<Block>, content_frame: Option<GeoRect>) -> Option<AlternateJson> {
+    if blocks.is_empty() && content_frame.is_none() {
+        return None;
+    }
+
+    Some(AlternateJson {
+        common_objects: blocks,
+        content_frame,
+    })
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(rename = "class", description = "document.styles に定義されたスタイルクラス（指定順に適用）")]
+pub enum ClassJson {
+    Name(#[schemars(length(min = 1))] String),
+    Names(#[schemars(inner(length(min = 1)))] Vec<String>),
+}
+
+// NOTE: スキーマは type ごとの定義の anyOf（document_schema）
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlockJson {
+    Text(TextJson),
+    Image(ImageJson),
+    Line(LineJson),
+    Rectangle(RectangleJson),
+    Objects(ObjectsJson),
+    Flexible(FlexibleJson),
+    FlexibleItem(FlexibleItemJson),
+    Object(ObjectJson),
+    Component(ComponentJson),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "text", transform = object_type("text"))]
+pub struct TextJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[schemars(length(min = 1))]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1.0))]
+    pub font_size: Option<f32>, // NOTE: PT（クラスや親要素から継承する場合は省略できる）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::text_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "image", transform = object_type("image"))]
+pub struct ImageJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[schemars(length(min = 1))]
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::image_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "line", transform = object_type("line"))]
+pub struct LineJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    pub frame: GeoRect, // NOTE: frame.size で始点からの長さを指定する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::line_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "rectangle", transform = object_type("rectangle"))]
+pub struct RectangleJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::rectangle_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "objects", transform = object_type("objects"))]
+pub struct ObjectsJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::inherited_style")]
+    pub style: Option<StyleClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+    #[schemars(schema_with = "document_schema::container_objects")]
+    pub objects: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "flexible", transform = object_type("flexible"))]
+pub struct FlexibleJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::inherited_style")]
+    pub style: Option<StyleClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+    #[schemars(schema_with = "document_schema::flexible_objects")]
+    pub objects: Vec<Block>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "flexible_item", transform = object_type("flexible_item"))]
+pub struct FlexibleItemJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::inherited_style")]
+    pub style: Option<StyleClassJson>,
+    #[schemars(schema_with = "document_schema::flexible_item_object")]
+    pub object: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 0.0, max = 100.0))]
+    pub basis: Option<f32>, // NOTE: %
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "object", transform = object_type("object"))]
+pub struct ObjectJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[schemars(schema_with = "document_schema::wrapped_object")]
+    pub object: Block,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::wrapper_style")]
+    pub style: Option<StyleClassJson>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(
+    rename = "component",
+    description = "document.components に定義されたコンポーネントを配置する",
+    transform = object_type("component")
+)]
+pub struct ComponentJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "document_schema::inherited_style")]
+    pub style: Option<StyleClassJson>,
+    #[schemars(length(min = 1))]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Map<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<GeoRect>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "component_definition")]
+pub struct ComponentDefinitionJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "引数の既定値")]
+    pub params: Option<Map<String, Value>>,
+    #[schemars(schema_with = "document_schema::component_object")]
+    pub object: Map<String, Value>,
+}
+
+fn check_expanded(class: Option<ClassJson>, inherited_style: Option<StyleClassJson>) -> Result<(), String> {
+    if class.is_some() {
+        return Err(template_error("class"));
+    }
+    if inherited_style.is_some() {
+        return Err(template_error("style on a container"));
+    }
+
+    Ok(())
+}
+
+impl TryFrom<BlockJson> for Block {
+    type Error = String;
+
+    fn try_from(json: BlockJson) -> Result<Block, String> {
+        let block = match json {
+            BlockJson::Text(json) => {
+                check_expanded(json.class, None)?;
+                let font_size = json.font_size.ok_or("text requires font_size".to_string())?;
+                let mut text = Text::new(json.text, font_size, json.font_path, json.frame);
+                StyleClassJson::into_styles(json.style, TEXT_STYLE_KEYS)?
+                    .into_iter()
+                    .for_each(|style| text.add_style(style));
+                Block::Text(text)
+            }
+            BlockJson::Image(json) => {
+                check_expanded(json.class, None)?;
+                let mut image = Image::new(json.path, json.frame);
+                StyleClassJson::into_styles(json.style, IMAGE_STYLE_KEYS)?
+                    .into_iter()
+                    .for_each(|style| image.add_style(style));
+                Block::Image(image)
+            }
+            BlockJson::Line(json) => {
+                check_expanded(json.class, None)?;
+                if json.frame.size.is_none() {
+                    return Err("line requires frame.size".to_string());
+                }
+                let mut line = Line::new(json.frame);
+                StyleClassJson::into_styles(json.style, LINE_STYLE_KEYS)?
+                    .into_iter()
+                    .for_each(|style| line.add_style(style));
+                Block::Line(line)
+            }
+            BlockJson::Rectangle(json) => {
+                check_expanded(json.class, None)?;
+                let mut rectangle = Rectangle::new(json.frame);
+                StyleClassJson::into_styles(json.style, RECTANGLE_STYLE_KEYS)?
+                    .into_iter()
+                    .for_each(|style| rectangle.add_style(style));
+                Block::Rectangle(rectangle)
+            }
+            BlockJson::Objects(json) => {
+                check_expanded(json.class, json.style)?;
+                let mut container = BlockContainer::new(json.frame);
+                if let Some(direction) = json.direction {
+                    container.set_direction(direction);
+                }
+                json.objects.into_iter().for_each(|block| container.add_block(block));
+                Block::Container(container)
+            }
+            BlockJson::Flexible(json) => {
+                check_expanded(json.class, json.style)?;
+                let mut container = FlexibleContainer::new(json.frame);
+                if let Some(direction) = json.direction {
+                    container.set_direction(direction);
+                }
+                json.objects.into_iter().for_each(|block| container.add_block(block));
+                Block::Flexible(container)
+            }
+            BlockJson::FlexibleItem(json) => {
+                check_expanded(json.class, json.style)?;
+                Block::FlexibleItem(Box::from(FlexibleItem::new(json.object, json.basis)))
+            }
+            BlockJson::Object(json) => {
+                check_expanded(json.class, None)?;
+                let mut wrapper = Wrapper::new(json.object);
+                StyleClassJson::into_styles(json.style, WRAPPER_STYLE_KEYS)?
+                    .into_iter()
+                    .for_each(|style| wrapper.add_style(style));
+                Block::Wrapper(Box::from(wrapper))
+            }
+            BlockJson::Component(_) => return Err(template_error("component")),
+        };
+
+        Ok(block)
+    }
+}
+
+// NOTE: レイアウトで計算される frame（flexible_item / object）や入力 JSON 上の位置は出力しない
+impl From<Block> for BlockJson {
+    fn from(block: Block) -> BlockJson {
+        match block {
+            Block::Text(text) => BlockJson::Text(TextJson {
+                class: None,
+                text: text.text,
+                font_size: Some(text.font_size),
+                font_path: text.font_path,
+                frame: text.frame,
+                style: StyleClassJson::from_styles(text.styles),
+            }),
+            Block::Image(image) => BlockJson::Image(ImageJson {
+                class: None,
+                path: image.path,
+                frame: image.frame,
+                style: StyleClassJson::from_styles(image.styles),
+            }),
+            Block::Line(line) => BlockJson::Line(LineJson {
+                class: None,
+                frame: line.frame,
+                style: StyleClassJson::from_styles(line.styles),
+            }),
+            Block::Rectangle(rectangle) => BlockJson::Rectangle(RectangleJson {
+                class: None,
+                frame: rectangle.frame,
+                style: StyleClassJson::from_styles(rectangle.styles),
+            }),
+            Block::Container(container) => BlockJson::Objects(ObjectsJson {
+                class: None,
+                style: None,
+                frame: container.frame,
+                direction: Some(container.direction),
+                objects: container.blocks,
+            }),
+            Block::Flexible(container) => BlockJson::Flexible(FlexibleJson {
+                class: None,
+                style: None,
+                frame: container.frame,
+                direction: Some(container.direction),
+                objects: container.blocks,
+            }),
+            Block::FlexibleItem(item) => BlockJson::FlexibleItem(FlexibleItemJson {
+                class: None,
+                style: None,
+                object: item.block,
+                basis: item.basis,
+            }),
+            Block::Wrapper(wrapper) => BlockJson::Object(ObjectJson {
+                class: None,
+                object: wrapper.block,
+                style: StyleClassJson::from_styles(wrapper.styles),
+            }),
+        }
+    }
+}
+
+// NOTE: "style" オブジェクトとスタイルクラス（要素ごとに使用できるキーは document_json の *_STYLE_KEYS）
+// NOTE: font_size / font_path はクラスやコンテナから継承する場合のみ使用する
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "style_class")]
+pub struct StyleClassJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1.0))]
+    pub font_size: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(length(min = 1))]
+    pub font_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alignment: Option<Alignment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<RgbColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<RgbColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_width: Option<BorderWidthJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_style: Option<LineStyleJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<Space>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_fill_color: Option<RgbColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_outline_color: Option<RgbColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_style: Option<TextStyleJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_outline_style: Option<LineStyleJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_wrap: Option<TextWrap>,
+}
+
+impl StyleClassJson {
+    // NOTE: document_json と同じく keys の順に Style を作る（null は未指定として扱う）
+    pub(crate) fn into_styles(style_json: Option<StyleClassJson>, keys: &[&str]) -> Result<Vec<Style>, String> {
+        let Some(mut style_json) = style_json else {
+            return Ok(Vec::new());
+        };
+
+        let mut styles = Vec::new();
+        for key in keys {
+            let style = match *key {
+                STYLE_KEY_ALIGNMENT => style_json.alignment.take().map(StyleJson::Alignment),
+                STYLE_KEY_BACKGROUND_COLOR => style_json.background_color.take().map(StyleJson::BackgroundColor),
+                STYLE_KEY_BORDER_COLOR => style_json.border_color.take().map(StyleJson::BorderColor),
+                STYLE_KEY_BORDER_STYLE => style_json.border_style.take().map(StyleJson::BorderStyle),
+                STYLE_KEY_BORDER_WIDTH => style_json.border_width.take().map(StyleJson::BorderWidth),
+                STYLE_KEY_SPACE => style_json.space.take().map(StyleJson::Space),
+                STYLE_KEY_TEXT_FILL_COLOR => style_json.text_fill_color.take().map(StyleJson::TextFillColor),
+                STYLE_KEY_TEXT_OUTLINE_COLOR => style_json.text_outline_color.take().map(StyleJson::TextOutlineColor),
+                STYLE_KEY_TEXT_OUTLINE_STYLE => style_json.text_outline_style.take().map(StyleJson::TextOutlineStyle),
+                STYLE_KEY_TEXT_STYLE => style_json.text_style.take().map(StyleJson::TextStyle),
+                STYLE_KEY_TEXT_WRAP => style_json.text_wrap.take().map(StyleJson::TextWrap),
+                _ => None,
+            };
+            if let Some(style) = style {
+                styles.push(Style::try_from(style).map_err(|e| format!("style.{}: {}", key, e))?);
+            }
+        }
+
+        Ok(styles)
+    }
+
+    // NOTE: スタイルがない場合は "style" を出力しない
+    fn from_styles(styles: Vec<Style>) -> Option<StyleClassJson> {
+        if styles.is_empty() {
+            return None;
+        }
+
+        let mut style_json = StyleClassJson::default();
+        for style in styles {
+            match StyleJson::from(style) {
+                StyleJson::TextFillColor(color) => style_json.text_fill_color = Some(color),
+                StyleJson::TextOutlineColor(color) => style_json.text_outline_color = Some(color),
+                StyleJson::TextStyle(text_style) => style_json.text_style = Some(text_style),
+                StyleJson::TextOutlineStyle(line_style) => style_json.text_outline_style = Some(line_style),
+                StyleJson::BackgroundColor(color) => style_json.background_color = Some(color),
+                StyleJson::BorderColor(color) => style_json.border_color = Some(color),
+                StyleJson::BorderWidth(border_width) => style_json.border_width = Some(border_width),
+                StyleJson::BorderStyle(line_style) => style_json.border_style = Some(line_style),
+                StyleJson::Space(space) => style_json.space = Some(space),
+                StyleJson::Alignment(alignment) => style_json.alignment = Some(alignment),
+                StyleJson::TextWrap(text_wrap) => style_json.text_wrap = Some(text_wrap),
+            }
+        }
+
+        Some(style_json)
+    }
+}
+
+// NOTE: Style 単体は1つのキーを持つオブジェクト（例: {"border_width": {"width": 0.5}}）
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum StyleJson {
+    TextFillColor(RgbColor),
+    TextOutlineColor(RgbColor),
+    TextStyle(TextStyleJson),
+    TextOutlineStyle(LineStyleJson),
+    BackgroundColor(RgbColor),
+    BorderColor(RgbColor),
+    BorderWidth(BorderWidthJson),
+    BorderStyle(LineStyleJson),
+    Space(Space),
+    Alignment(Alignment),
+    TextWrap(TextWrap),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "text_style")]
+pub struct TextStyleJson {
+    pub line_style: TextLineStyle,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(inline)]
+pub enum TextLineStyle {
+    Fill,
+    Stroke,
+    FillStroke,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "line_style")]
+pub struct LineStyleJson {
+    pub line_style: LineStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1))]
+    pub dash_1: Option<i64>, // NOTE: line_style が dash の場合は必須
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(inline)]
+pub enum LineStyle {
+    Solid,
+    Dash,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "border_width")]
+pub struct BorderWidthJson {
+    #[schemars(range(min = 0.0))]
+    pub width: f32, // NOTE: mm
+}
+
+impl LineStyleJson {
+    fn solid() -> LineStyleJson {
+        LineStyleJson { line_style: LineStyle::Solid, dash_1: None }
+    }
+
+    fn dash(dash_1: i64) -> LineStyleJson {
+        LineStyleJson { line_style: LineStyle::Dash, dash_1: Some(dash_1) }
+    }
+
+    // NOTE: 実線の場合は None
+    fn dash_1(&self) -> Result<Option<i64>, String> {
+        match (&self.line_style, self.dash_1) {
+            (LineStyle::Solid, _) => Ok(None),
+            (LineStyle::Dash, Some(dash_1)) => Ok(Some(dash_1)),
+            (LineStyle::Dash, None) => Err("dash_1 is required when line_style is dash".to_string()),
+        }
+    }
+}
+
+impl TryFrom<StyleJson> for Style {
+    type Error = String;
+
+    fn try_from(json: StyleJson) -> Result<Style, String> {
+        let style = match json {
+            StyleJson::TextFillColor(color) => Style::TextFillColor(color),
+            StyleJson::TextOutlineColor(color) => Style::TextOutlineColor(color),
+            StyleJson::TextStyle(text_style) => Style::TextStyle(match text_style.line_style {
+                TextLineStyle::Fill => TextStyle::Fill,
+                TextLineStyle::Stroke => TextStyle::Stroke,
+                TextLineStyle::FillStroke => TextStyle::FillStroke,
+            }),
+            StyleJson::TextOutlineStyle(line_style) => Style::TextOutlineStyle(match line_style.dash_1()? {
+                Some(dash_1) => TextOutlineStyle::Dash(dash_1),
+                None => TextOutlineStyle::Solid,
+            }),
+            StyleJson::BackgroundColor(color) => Style::BackgroundColor(color),
+            StyleJson::BorderColor(color) => Style::BorderColor(color),
+            StyleJson::BorderWidth(border_width) => Style::BorderWidth(border_width.width),
+            StyleJson::BorderStyle(line_style) => Style::BorderStyle(match line_style.dash_1()? {
+                Some(dash_1) => BorderStyle::Dash(dash_1),
+                None => BorderStyle::Solid,
+            }),
+            StyleJson::Space(space) => Style::Space(space),
+            StyleJson::Alignment(alignment) => Style::Alignment(alignment),
+            StyleJson::TextWrap(text_wrap) => Style::TextWrap(text_wrap),
+        };
+
+        Ok(style)
+    }
+}
+
+impl From<Style> for StyleJson {
+    fn from(style: Style) -> StyleJson {
+        match style {
+            Style::TextFillColor(color) => StyleJson::TextFillColor(color),
+            Style::TextOutlineColor(color) => StyleJson::TextOutlineColor(color),
+            Style::TextStyle(text_style) => StyleJson::TextStyle(TextStyleJson {
+                line_style: match text_style {
+                    TextStyle::Fill => TextLineStyle::Fill,
+                    TextStyle::Stroke => TextLineStyle::Stroke,
+                    TextStyle::FillStroke => TextLineStyle::FillStroke,
+                },
+            }),
+            Style::TextOutlineStyle(TextOutlineStyle::Solid) => StyleJson::TextOutlineStyle(LineStyleJson::solid()),
+            Style::TextOutlineStyle(TextOutlineStyle::Dash(dash_1)) => {
+                StyleJson::TextOutlineStyle(LineStyleJson::dash(dash_1))
+            }
+            Style::BackgroundColor(color) => StyleJson::BackgroundColor(color),
+            Style::BorderColor(color) => StyleJson::BorderColor(color),
+            Style::BorderWidth(width) => StyleJson::BorderWidth(BorderWidthJson { width }),
+            Style::BorderStyle(BorderStyle::Solid) => StyleJson::BorderStyle(LineStyleJson::solid()),
+            Style::BorderStyle(BorderStyle::Dash(dash_1)) => StyleJson::BorderStyle(LineStyleJson::dash(dash_1)),
+            Style::Space(space) => StyleJson::Space(space),
+            Style::Alignment(alignment) => StyleJson::Alignment(alignment),
+            Style::TextWrap(text_wrap) => StyleJson::TextWrap(text_wrap),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::block_document::block::Block;
+use crate::block_document::data_uri;
 use crate::block_document::direction::Direction;
 use crate::block_document::document::px_to_mm;
 use crate::block_document::geometry::{GeoPoint, GeoRect, GeoSize};
 use crate::block_document::image::Image;
-use crate::block_document::style::{Style, TextWrapMode};
+use crate::block_document::page_number::PageNumbering;
+use crate::block_document::resource::load_image;
+use crate::block_document::style::{Space, Style, TextWrapMode};
 use crate::block_document::text::Text;
 use crate::block_document::text_renderer::{measure_text, wrap_text_by_character};
 use image::{GenericImageView, ImageError};
@@ -15,6 +18,21 @@ pub struct DynamicPage {
     pub content_blocks: Vec<Block>,
     pub continuation_common_blocks: Option<Vec<Block>>,
     pub continuation_content_frame: Option<GeoRect>,
+    pub first_common_blocks: Vec<Block>, // NOTE: 最初のページのみ
+    pub last_common_blocks: Vec<Block>,  // NOTE: 最後のページのみ
+    pub odd_common_blocks: Vec<Block>,   // NOTE: 奇数ページのみ
+    pub odd_content_frame: Option<GeoRect>,
+    pub even_common_blocks: Vec<Block>, // NOTE: 偶数ページのみ
+    pub even_content_frame: Option<GeoRect>,
+    pub page_numbering: PageNumbering,
+    pub page_size: Option<GeoSize>,
+    pub margin: Option<Space>,
+}
+
+impl Default for DynamicPage {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DynamicPage {
@@ -25,6 +43,15 @@ impl DynamicPage {
             content_blocks: Vec::new(),
             continuation_common_blocks: None,
             continuation_content_frame: None,
+            first_common_blocks: Vec::new(),
+            last_common_blocks: Vec::new(),
+            odd_common_blocks: Vec::new(),
+            odd_content_frame: None,
+            even_common_blocks: Vec::new(),
+            even_content_frame: None,
+            page_numbering: PageNumbering::default(),
+            page_size: None,
+            margin: None,
         }
     }
 
@@ -54,53 +81,53 @@ impl DynamicPage {
         self.continuation_content_frame = Some(frame);
     }
 
+    pub fn add_first_common_block(&mut self, block: Block) {
+        self.first_common_blocks.push(block);
+    }
+
+    pub fn add_last_common_block(&mut self, block: Block) {
+        self.last_common_blocks.push(block);
+    }
+
+    pub fn add_odd_common_block(&mut self, block: Block) {
+        self.odd_common_blocks.push(block);
+    }
+
+    pub fn set_odd_content_frame(&mut self, frame: GeoRect) {
+        self.odd_content_frame = Some(frame);
+    }
+
+    pub fn add_even_common_block(&mut self, block: Block) {
+        self.even_common_blocks.push(block);
+    }
+
+    pub fn set_even_content_frame(&mut self, frame: GeoRect) {
+        self.even_content_frame = Some(frame);
+    }
+
+    pub fn set_page_numbering(&mut self, page_numbering: PageNumbering) {
+        self.page_numbering = page_numbering;
+    }
+
+    pub fn set_page_size(&mut self, page_size: GeoSize) {
+        self.page_size = Some(page_size);
+    }
+
+    pub fn set_margin(&mut self, margin: Space) {
+        self.margin = Some(margin);
+    }
+
     // NOTE: 座標を計算する
+    // NOTE: first_page_number はドキュメント全体でのページ番号（奇数・偶数の判定に使用）
     pub fn apply_constraints(
         &mut self,
         parent_frame: &GeoRect,
         direction: &Direction,
         font_path: &String,
+        first_page_number: usize,
     ) -> Vec<DynamicPage> {
-        let mut common_drawn_frame = GeoRect::new(0.0, 0.0, parent_frame.min_x(), parent_frame.min_y());
-
-        let mut common_blocks = self.common_blocks.clone();
-        for block in common_blocks.iter_mut() {
-            let (is_fixed, frame) = Self::apply_block_constraints(
-                block,
-                &parent_frame,
-                &common_drawn_frame,
-                direction,
-                font_path,
-            );
-            if is_fixed {
-                continue;
-            }
-
-            common_drawn_frame = common_drawn_frame.union(frame.as_ref().unwrap_or(&GeoRect::default()));
-        }
-
-        let mut continuation_common_blocks = self.continuation_common_blocks.clone();
-        if let Some(ref mut a_common_blocks) = continuation_common_blocks {
-            let mut continuation_common_drawn_frame = GeoRect::new(0.0, 0.0, parent_frame.min_x(), parent_frame.min_y());
-            for block in a_common_blocks.iter_mut() {
-                let (is_fixed, frame) = Self::apply_block_constraints(
-                    block,
-                    &parent_frame,
-                    &continuation_common_drawn_frame,
-                    direction,
-                    font_path,
-                );
-                if is_fixed {
-                    continue;
-                }
-
-                continuation_common_drawn_frame = continuation_common_drawn_frame.union(frame.as_ref().unwrap_or(&GeoRect::default()));
-            }
-        }
-
         let mut containers: Vec<DynamicPage> = Vec::new();
-        let mut content_frame = self.content_frame.clone();
-        let continuation_content_frame = self.continuation_content_frame.clone();
+        let mut content_frame = self.content_frame_at(0, first_page_number);
         let mut content_drawn_frame = GeoRect::new(0.0, 0.0, content_frame.min_x(), content_frame.min_y());
         let mut content_blocks: Vec<Block> = Vec::new();
 
@@ -120,21 +147,9 @@ impl DynamicPage {
             }
 
             if content_frame.max_y() < content_drawn_frame.max_y() + frame.as_ref().unwrap_or(&GeoRect::default()).height() {
-                containers.push(DynamicPage {
-                    common_blocks: common_blocks.clone(),
-                    content_frame: content_frame.clone(),
-                    content_blocks,
-                    continuation_common_blocks: None,
-                    continuation_content_frame: None,
-                });
-
-                if continuation_common_blocks.is_some() {
-                    common_blocks = continuation_common_blocks.clone().unwrap();
-                }
+                containers.push(self.with_content(content_frame, content_blocks));
 
-                if continuation_content_frame.is_some() {
-                    content_frame = continuation_content_frame.clone().unwrap();
-                }
+                content_frame = self.content_frame_at(containers.len(), first_page_number + containers.len());
 
                 content_drawn_frame = GeoRect::new(0.0, 0.0, content_frame.min_x(), content_frame.min_y());
 
@@ -161,18 +176,104 @@ impl DynamicPage {
         }
 
         if content_blocks.len() > 0 {
-            containers.push(DynamicPage {
-                common_blocks,
-                content_frame,
-                content_blocks,
-                continuation_common_blocks: None,
-                continuation_content_frame: None,
-            });
+            containers.push(self.with_content(content_frame, content_blocks));
+        }
+
+        // NOTE: ページの位置に応じて共通要素を決める
+        let page_count = containers.len();
+        for (i, container) in containers.iter_mut().enumerate() {
+            let mut blocks = match (i, &self.continuation_common_blocks) {
+                (0, _) | (_, None) => self.common_blocks.clone(),
+                (_, Some(continuation_common_blocks)) => continuation_common_blocks.clone(),
+            };
+
+            if i == 0 {
+                blocks.extend(self.first_common_blocks.iter().cloned());
+            }
+
+            if (first_page_number + i) % 2 == 1 {
+                blocks.extend(self.odd_common_blocks.iter().cloned());
+            } else {
+                blocks.extend(self.even_common_blocks.iter().cloned());
+            }
+
+            if i == page_count - 1 {
+                blocks.extend(self.last_common_blocks.iter().cloned());
+            }
+
+            container.common_blocks = Self::apply_common_constraints(&blocks, parent_frame, direction, font_path);
         }
 
         containers
     }
 
+    fn apply_common_constraints(
+        blocks: &[Block],
+        parent_frame: &GeoRect,
+        direction: &Direction,
+        font_path: &String,
+    ) -> Vec<Block> {
+        let mut common_drawn_frame = GeoRect::new(0.0, 0.0, parent_frame.min_x(), parent_frame.min_y());
+
+        let mut common_blocks = blocks.to_vec();
+        for block in common_blocks.iter_mut() {
+            let (is_fixed, frame) = Self::apply_block_constraints(
+                block,
+                parent_frame,
+                &common_drawn_frame,
+                direction,
+                font_path,
+            );
+            if is_fixed {
+                continue;
+            }
+
+            common_drawn_frame = common_drawn_frame.union(frame.as_ref().unwrap_or(&GeoRect::default()));
+        }
+
+        common_blocks
+    }
+
+    // NOTE: page_index はこのページ定義内での位置、page_number はドキュメント全体でのページ番号
+    // NOTE: 奇数・偶数の content_frame → 2ページ目以降は continuation.content_frame → content.frame の順に使用する
+    fn content_frame_at(&self, page_index: usize, page_number: usize) -> GeoRect {
+        let parity_content_frame = if page_number % 2 == 1 {
+            &self.odd_content_frame
+        } else {
+            &self.even_content_frame
+        };
+        let continuation_content_frame = if page_index == 0 {
+            &None
+        } else {
+            &self.continuation_content_frame
+        };
+
+        parity_content_frame
+            .clone()
+            .or(continuation_content_frame.clone())
+            .unwrap_or(self.content_frame.clone())
+    }
+
+    // NOTE: ページ設定を引き継いだ描画用のページを作る（共通要素は後で設定する）
+    fn with_content(&self, content_frame: GeoRect, content_blocks: Vec<Block>) -> DynamicPage {
+        DynamicPage {
+            common_blocks: Vec::new(),
+            content_frame,
+            content_blocks,
+            continuation_common_blocks: None,
+            continuation_content_frame: None,
+            first_common_blocks: Vec::new(),
+            last_common_blocks: Vec::new(),
+            odd_common_blocks: Vec::new(),
+            odd_content_frame: None,
+            even_common_blocks: Vec::new(),
+            even_content_frame: None,
+            page_numbering: self.page_numbering.clone(),
+            page_size: self.page_size.clone(),
+            margin: self.margin.clone(),
+        }
+    }
+
     fn apply_block_constraints(
         block: &mut Block,
         parent_frame: &GeoRect,
@@ -643,11 +744,7 @@ impl DynamicPage {
     }
 
     fn get_image_dimensions(path: &str) -> Result<(u32, u32), ImageError> {
-        image::io::Reader::open(path)
-            .map_err(|e| ImageError::from(e))?
-            .decode()
-            .map(|image| image.dimensions())
-            .map_err(ImageError::from)
+        load_image(path).map(|image| image.dimensions())
     }
 
     fn calculate_image_constraints(
@@ -691,7 +788,8 @@ impl DynamicPage {
                 Err(e) => {
                     panic!(
                         "Error reading image dimensions for {}: {}",
-                        block_image.path, e
+                        data_uri::display(&block_image.path),
+                        e
                     );
                 }
             }
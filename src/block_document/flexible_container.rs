@@ -7,6 +7,7 @@ pub struct FlexibleContainer {
     pub blocks: Vec<Block>,
     pub frame: Option<GeoRect>,
     pub direction: Direction,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl FlexibleContainer {
@@ -15,6 +16,7 @@ impl FlexibleContainer {
             blocks: Vec::new(),
             frame,
             direction: Direction::Horizontal,
+            source_pointer: None,
         }
     }
 
@@ -29,4 +31,8 @@ impl FlexibleContainer {
     pub fn set_frame(&mut self, frame: GeoRect) {
         self.frame = Some(frame);
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
@@ -5,7 +5,8 @@ use crate::block_document::geometry::GeoRect;
 pub struct FlexibleItem {
     pub block: Block,
     pub frame: Option<GeoRect>,
-    pub basis: Option<f32>
+    pub basis: Option<f32>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl FlexibleItem {
@@ -13,11 +14,16 @@ impl FlexibleItem {
         FlexibleItem {
             block,
             frame: None,
-            basis
+            basis,
+            source_pointer: None,
         }
     }
 
     pub fn set_frame(&mut self, frame: GeoRect) {
         self.frame = Some(frame);
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
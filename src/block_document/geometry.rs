@@ -1,20 +1,31 @@
 use crate::block_document::style::Space;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "frame")]
 pub struct GeoRect {
+    #[schemars(required, schema_with = "crate::block_document::document_schema::nullable::<GeoPoint>")]
     pub point: Option<GeoPoint>,
+    #[schemars(required, schema_with = "crate::block_document::document_schema::nullable::<GeoSize>")]
     pub size: Option<GeoSize>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "point")]
 pub struct GeoPoint {
+    #[schemars(range(min = 0.0))]
     pub x: f32, // NOTE: mm
+    #[schemars(range(min = 0.0))]
     pub y: f32, // NOTE: mm
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "size")]
 pub struct GeoSize {
-    pub width: f32,  // NOTE: mm
+    #[schemars(range(min = 0.0))]
+    pub width: f32, // NOTE: mm
+    #[schemars(range(min = 0.0))]
     pub height: f32, // NOTE: mm
 }
 
@@ -69,23 +80,23 @@ impl GeoRect {
             + self.size.as_ref().unwrap_or(&Default::default()).height
     }
 
-    // // NOTE: 内側に余白を作る
-    // pub fn inset(&self, space: &Space) -> GeoRect {
-    //     GeoRect {
-    //         point: Some(GeoPoint {
-    //             x: self.point.as_ref().unwrap_or(&Default::default()).x + space.left,
-    //             y: self.point.as_ref().unwrap_or(&Default::default()).y + space.top,
-    //         }),
-    //         size: Some(GeoSize {
-    //             width: self.size.as_ref().unwrap_or(&Default::default()).width
-    //                 - space.left
-    //                 - space.right,
-    //             height: self.size.as_ref().unwrap_or(&Default::default()).height
-    //                 - space.top
-    //                 - space.bottom,
-    //         }),
-    //     }
-    // }
+    // NOTE: 内側に余白を作る
+    pub fn inset(&self, space: &Space) -> GeoRect {
+        GeoRect {
+            point: Some(GeoPoint {
+                x: self.point.as_ref().unwrap_or(&Default::default()).x + space.left,
+                y: self.point.as_ref().unwrap_or(&Default::default()).y + space.top,
+            }),
+            size: Some(GeoSize {
+                width: self.size.as_ref().unwrap_or(&Default::default()).width
+                    - space.left
+                    - space.right,
+                height: self.size.as_ref().unwrap_or(&Default::default()).height
+                    - space.top
+                    - space.bottom,
+            }),
+        }
+    }
 
     // NOTE: 外側に余白を作る
     pub fn padding(&self, space: &Space) -> GeoRect {
@@ -6,6 +6,7 @@ pub struct Image {
     pub path: String,
     pub frame: Option<GeoRect>,
     pub styles: Vec<Style>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl Image {
@@ -14,6 +15,7 @@ impl Image {
             path,
             frame,
             styles: Vec::new(),
+            source_pointer: None,
         }
     }
 
@@ -24,4 +26,8 @@ impl Image {
     pub fn set_frame(&mut self, frame: GeoRect) {
         self.frame = Some(frame);
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
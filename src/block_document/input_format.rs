@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+use serde_json::Value;
+use std::fs::read_to_string;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::process::exit;
+
+// NOTE: 入力ファイルの形式（いずれも serde_json::Value に変換してからスキーマ検証する）
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Json,
+    Json5,
+    Yaml,
+    Toml,
+}
+
+impl InputFormat {
+    // NOTE: 不明な拡張子の場合は JSON として扱う
+    pub fn from_path(path: &str) -> InputFormat {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+
+        match extension.as_deref() {
+            Some("json5") => InputFormat::Json5,
+            Some("yaml") | Some("yml") => InputFormat::Yaml,
+            Some("toml") => InputFormat::Toml,
+            _ => InputFormat::Json,
+        }
+    }
+
+    pub fn parse(&self, string: &str) -> Result<Value, String> {
+        match self {
+            InputFormat::Json => serde_json::from_str(string).map_err(|e| e.to_string()),
+            InputFormat::Json5 => json5::from_str(string).map_err(|e| e.to_string()),
+            InputFormat::Yaml => serde_yaml::from_str(string).map_err(|e| e.to_string()),
+            InputFormat::Toml => toml::from_str(string).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+pub const STDIN_PATH: &str = "-";
+
+// NOTE: format が指定されていない場合は拡張子で判定する（"-" の場合は標準入力から読み込む）
+pub fn read(path: &str, format: Option<InputFormat>) -> Value {
+    parse_source(path, &read_source(path), format)
+}
+
+pub fn read_source(path: &str) -> String {
+    if path == STDIN_PATH {
+        let mut string = String::new();
+        io::stdin().read_to_string(&mut string).map(|_| string)
+    } else {
+        read_to_string(path)
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {}", path, e);
+        exit(1);
+    })
+}
+
+pub fn parse_source(path: &str, source: &str, format: Option<InputFormat>) -> Value {
+    let format = format.unwrap_or(InputFormat::from_path(path));
+    format.parse(source).unwrap_or_else(|e| {
+        eprintln!("Failed to parse {} as {:?}: {}", path, format, e);
+        exit(1);
+    })
+}
@@ -0,0 +1,107 @@
+use crate::block_document::asset_path::AssetPaths;
+use crate::block_document::block::Block;
+use crate::block_document::data_uri;
+use crate::block_document::geometry::GeoPoint;
+use crate::block_document::pdf_writer::LaidOutDocument;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+
+// NOTE: レイアウトの結果を JSON で保存する（テンプレートの版ごとの差分の確認やテスト用）
+pub fn save(path: &str, laid_out_documents: &[LaidOutDocument], asset_paths: &AssetPaths, allow_override: bool) {
+    if !allow_override && Path::new(path).exists() {
+        eprintln!("The layout dump path already exists! {}", path);
+        exit(1);
+    }
+
+    let string = serde_json::to_string_pretty(&to_json(laid_out_documents, asset_paths)).unwrap();
+    if let Err(e) = fs::write(path, string + "\n") {
+        eprintln!("Could not write layout dump! {}", e);
+        exit(1);
+    }
+}
+
+// NOTE: page_index は出力した PDF のページ（0 始まり）、frame はページの左上を基準にした mm
+// NOTE: 画像の path は入力ファイルのディレクトリ（または asset_root）からの相対パス
+pub fn to_json(laid_out_documents: &[LaidOutDocument], asset_paths: &AssetPaths) -> Value {
+    let mut pages: Vec<Value> = Vec::new();
+    for (document_index, laid_out_document) in laid_out_documents.iter().enumerate() {
+        for (i, page) in laid_out_document.pages.iter().enumerate() {
+            let page_size = page.page_size().unwrap_or(&laid_out_document.page_size);
+            let blocks: Vec<Value> = page
+                .drawn_blocks()
+                .into_iter()
+                .map(|block| block_json(block, &GeoPoint::default(), asset_paths))
+                .collect();
+
+            pages.push(json!({
+                "page_index": pages.len(),
+                "document_index": document_index,
+                "page_number": laid_out_document.page_counters[i].number,
+                "size": {
+                    "width": round(page_size.width),
+                    "height": round(page_size.height),
+                },
+                "blocks": blocks,
+            }));
+        }
+    }
+
+    json!({ "pages": pages })
+}
+
+// NOTE: origin は親の frame の左上（ページの左上基準）
+fn block_json(block: &Block, origin: &GeoPoint, asset_paths: &AssetPaths) -> Value {
+    let frame = block.frame();
+    let point = frame.and_then(|frame| frame.point.clone()).unwrap_or_default();
+    let x = origin.x + point.x;
+    let y = origin.y + point.y;
+
+    let mut object = Map::new();
+    object.insert("type".to_string(), json!(block.type_name()));
+    object.insert("source".to_string(), json!(block.source_pointer()));
+    object.insert(
+        "frame".to_string(),
+        match frame {
+            Some(frame) => json!({
+                "x": round(x),
+                "y": round(y),
+                "width": frame.size.as_ref().map(|size| round(size.width)),
+                "height": frame.size.as_ref().map(|size| round(size.height)),
+            }),
+            None => Value::Null,
+        },
+    );
+
+    match block {
+        Block::Text(text) => {
+            object.insert("text".to_string(), json!(text.text));
+            object.insert("font_size".to_string(), json!(round(text.font_size)));
+            object.insert("lines".to_string(), json!(text.get_display_text()));
+            if let Some(wrapped_text) = text.get_wrapped_text() {
+                object.insert("is_truncated".to_string(), json!(wrapped_text.is_truncated));
+            }
+        }
+        Block::Image(image) => {
+            object.insert("path".to_string(), json!(data_uri::display(&asset_paths.relative(&image.path))));
+        }
+        _ => {}
+    }
+
+    let children = block.children();
+    if !children.is_empty() {
+        let child_origin = GeoPoint { x, y };
+        object.insert(
+            "children".to_string(),
+            Value::Array(children.into_iter().map(|child| block_json(child, &child_origin, asset_paths)).collect()),
+        );
+    }
+
+    Value::Object(object)
+}
+
+// NOTE: f32 の誤差で差分が出ないように 0.001 mm で丸める
+fn round(value: f32) -> f64 {
+    (value as f64 * 1000.0).round() / 1000.0
+}
@@ -6,6 +6,7 @@ use crate::block_document::style::Style;
 pub struct Line {
     pub frame: GeoRect,
     pub styles: Vec<Style>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl Line {
@@ -17,6 +18,7 @@ impl Line {
         Line {
             frame,
             styles: Vec::new(),
+            source_pointer: None,
         }
     }
 
@@ -27,4 +29,8 @@ impl Line {
     pub fn set_frame(&mut self, frame: GeoRect) {
         self.frame = frame;
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
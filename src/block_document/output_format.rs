@@ -0,0 +1,24 @@
+use clap::ValueEnum;
+use std::path::Path;
+
+// NOTE: 出力ファイルの形式（PNG はプレビューや画像の差分によるテスト用）
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Pdf,
+    Png,
+}
+
+impl OutputFormat {
+    // NOTE: 不明な拡張子や標準出力の場合は PDF として扱う
+    pub fn from_path(path: &str) -> OutputFormat {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+
+        match extension.as_deref() {
+            Some("png") => OutputFormat::Png,
+            _ => OutputFormat::Pdf,
+        }
+    }
+}
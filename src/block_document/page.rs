@@ -1,8 +1,69 @@
+use crate::block_document::block::Block;
 use crate::block_document::dynamic_page::DynamicPage;
+use crate::block_document::geometry::{GeoRect, GeoSize};
 use crate::block_document::static_page::StaticPage;
+use crate::block_document::style::Space;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "crate::block_document::document_serde::PageJson")]
+#[serde(into = "crate::block_document::document_serde::PageJson")]
 pub enum Page {
-    DynamicPage(DynamicPage),
+    DynamicPage(Box<DynamicPage>),
     StaticPage(StaticPage)
-}
\ No newline at end of file
+}
+
+impl Page {
+    pub fn page_size(&self) -> Option<&GeoSize> {
+        match self {
+            Page::DynamicPage(dynamic_page) => dynamic_page.page_size.as_ref(),
+            Page::StaticPage(static_page) => static_page.page_size.as_ref(),
+        }
+    }
+
+    pub fn margin(&self) -> Option<&Space> {
+        match self {
+            Page::DynamicPage(dynamic_page) => dynamic_page.margin.as_ref(),
+            Page::StaticPage(static_page) => static_page.margin.as_ref(),
+        }
+    }
+
+    pub fn blocks_mut(&mut self) -> Vec<&mut Block> {
+        match self {
+            Page::DynamicPage(dynamic_page) => {
+                let mut blocks: Vec<&mut Block> = Vec::new();
+                blocks.extend(dynamic_page.common_blocks.iter_mut());
+                blocks.extend(dynamic_page.content_blocks.iter_mut());
+                if let Some(continuation_common_blocks) = dynamic_page.continuation_common_blocks.as_mut() {
+                    blocks.extend(continuation_common_blocks.iter_mut());
+                }
+                blocks.extend(dynamic_page.first_common_blocks.iter_mut());
+                blocks.extend(dynamic_page.last_common_blocks.iter_mut());
+                blocks.extend(dynamic_page.odd_common_blocks.iter_mut());
+                blocks.extend(dynamic_page.even_common_blocks.iter_mut());
+                blocks
+            }
+            Page::StaticPage(static_page) => static_page.blocks.iter_mut().collect(),
+        }
+    }
+
+    // NOTE: レイアウト後のページで描画するブロック
+    pub fn drawn_blocks(&self) -> Vec<&Block> {
+        match self {
+            Page::DynamicPage(dynamic_page) => dynamic_page
+                .common_blocks
+                .iter()
+                .chain(dynamic_page.content_blocks.iter())
+                .collect(),
+            Page::StaticPage(static_page) => static_page.blocks.iter().collect(),
+        }
+    }
+
+    // NOTE: レイアウト後の動的ページでコンテンツを配置した領域（ページの左上基準）
+    pub fn content_frame(&self) -> Option<&GeoRect> {
+        match self {
+            Page::DynamicPage(dynamic_page) => Some(&dynamic_page.content_frame),
+            Page::StaticPage(_) => None,
+        }
+    }
+}
@@ -1,13 +1,22 @@
 use crate::block_document::geometry::GeoRect;
 use crate::block_document::style::Style;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+// NOTE: format が指定されていない場合の書式
+pub const DEFAULT_FORMAT: &str = "Page ${CURRENT_PAGE_NUMBER} of ${TOTAL_PAGES}";
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "crate::block_document::document_serde::PageNumberJson")]
+#[serde(into = "crate::block_document::document_serde::PageNumberJson")]
+#[schemars(rename = "page_number")]
 pub struct PageNumber {
     pub format: String,
     pub font_size: f32, // NOTE: PT
     pub font_path: Option<String>,
     pub frame: Option<GeoRect>,
     pub styles: Vec<Style>,
+    pub number_style: NumberStyle,
 }
 
 impl PageNumber {
@@ -18,10 +27,310 @@ impl PageNumber {
             font_path,
             frame,
             styles: Vec::new(),
+            number_style: NumberStyle::Decimal,
         }
     }
 
     pub fn add_style(&mut self, style: Style) {
         self.styles.push(style);
     }
-}
\ No newline at end of file
+
+    pub fn set_number_style(&mut self, number_style: NumberStyle) {
+        self.number_style = number_style;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(rename = "number_style")]
+pub enum NumberStyle {
+    #[default]
+    Decimal,
+    LowerRoman,
+    UpperRoman,
+    LowerAlpha,
+    UpperAlpha,
+    Kanji,
+}
+
+impl NumberStyle {
+    pub fn format(&self, number: u32) -> String {
+        match self {
+            NumberStyle::Decimal => number.to_string(),
+            NumberStyle::LowerRoman => to_roman(number).to_lowercase(),
+            NumberStyle::UpperRoman => to_roman(number),
+            NumberStyle::LowerAlpha => to_alpha(number).to_lowercase(),
+            NumberStyle::UpperAlpha => to_alpha(number),
+            NumberStyle::Kanji => to_kanji(number),
+        }
+    }
+}
+
+// NOTE: ページ定義（pages の各要素）単位のページ番号設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+#[schemars(rename = "page_numbering")]
+pub struct PageNumbering {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_style: Option<NumberStyle>, // NOTE: 未指定の場合はドキュメントの設定を使用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1), description = "Restart the page counter at this number")]
+    pub start: Option<u32>, // NOTE: 指定された場合はカウンターをリセット
+    #[schemars(description = "Shift the page counter by this amount")]
+    pub offset: i32,
+    #[schemars(description = "Exclude pages from numbering (e.g. cover pages)")]
+    pub exclude: bool, // NOTE: 表紙など、ページ番号を付与しない
+}
+
+// NOTE: 描画ページごとのページ番号
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCounter {
+    pub number: Option<u32>, // NOTE: None の場合はページ番号なし
+    pub number_style: NumberStyle,
+    pub section_page: u32,
+    pub section_pages: u32,
+}
+
+// NOTE: sections は (ページ定義のページ番号設定, ページ定義から生成されたページ数)
+pub fn count_pages(sections: &[(PageNumbering, usize)], default_style: &NumberStyle) -> Vec<PageCounter> {
+    let mut counters: Vec<PageCounter> = Vec::new();
+    let mut next_number: i64 = 1;
+
+    for (numbering, page_count) in sections.iter() {
+        let number_style = numbering.number_style.clone().unwrap_or(default_style.clone());
+
+        if let Some(start) = numbering.start {
+            next_number = start as i64;
+        }
+        next_number = (next_number + numbering.offset as i64).max(1);
+
+        for i in 0..*page_count {
+            let number = if numbering.exclude {
+                None
+            } else {
+                let number = next_number as u32;
+                next_number += 1;
+                Some(number)
+            };
+
+            counters.push(PageCounter {
+                number,
+                number_style: number_style.clone(),
+                section_page: i as u32 + 1,
+                section_pages: *page_count as u32,
+            });
+        }
+    }
+
+    counters
+}
+
+pub fn total_pages(counters: &[PageCounter]) -> u32 {
+    counters.iter().filter(|counter| counter.number.is_some()).count() as u32
+}
+
+// NOTE: 物理ページ番号と一致する場合は PageLabels が不要
+pub fn needs_page_labels(counters: &[PageCounter]) -> bool {
+    counters.iter().enumerate().any(|(i, counter)| {
+        counter.number != Some(i as u32 + 1) || counter.number_style != NumberStyle::Decimal
+    })
+}
+
+// NOTE: ローマ数字で表せない 0 と 4000 以上は算用数字にする
+fn to_roman(number: u32) -> String {
+    if number == 0 || number > 3999 {
+        return number.to_string();
+    }
+
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut rest = number;
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS.iter() {
+        while rest >= *value {
+            roman.push_str(numeral);
+            rest -= value;
+        }
+    }
+
+    roman
+}
+
+// NOTE: PDF の PageLabels と同じ形式（A..Z, AA..ZZ, AAA..）
+fn to_alpha(number: u32) -> String {
+    if number == 0 {
+        return number.to_string();
+    }
+
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    let repeat = ((number - 1) / 26 + 1) as usize;
+
+    letter.to_string().repeat(repeat)
+}
+
+fn to_kanji(number: u32) -> String {
+    const DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    const UNITS: [&str; 4] = ["", "十", "百", "千"];
+    const LARGE_UNITS: [&str; 3] = ["", "万", "億"];
+
+    if number == 0 {
+        return DIGITS[0].to_string();
+    }
+
+    let mut kanji = String::new();
+    let mut rest = number;
+    let mut groups: Vec<u32> = Vec::new();
+    while rest > 0 {
+        groups.push(rest % 10000);
+        rest /= 10000;
+    }
+
+    for (group_index, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+
+        for unit_index in (0..4).rev() {
+            let digit = (group / 10_u32.pow(unit_index as u32)) % 10;
+            if digit == 0 {
+                continue;
+            }
+
+            // NOTE: 十・百・千の前の「一」は省略する
+            if digit != 1 || unit_index == 0 {
+                kanji.push_str(DIGITS[digit as usize]);
+            }
+            kanji.push_str(UNITS[unit_index]);
+        }
+
+        kanji.push_str(LARGE_UNITS[group_index]);
+    }
+
+    kanji
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbering(start: Option<u32>, offset: i32, exclude: bool) -> PageNumbering {
+        PageNumbering {
+            number_style: None,
+            start,
+            offset,
+            exclude,
+        }
+    }
+
+    fn numbers(counters: &[PageCounter]) -> Vec<Option<u32>> {
+        counters.iter().map(|counter| counter.number).collect()
+    }
+
+    #[test]
+    fn roman() {
+        assert_eq!(to_roman(1), "I");
+        assert_eq!(to_roman(4), "IV");
+        assert_eq!(to_roman(9), "IX");
+        assert_eq!(to_roman(14), "XIV");
+        assert_eq!(to_roman(1994), "MCMXCIV");
+        assert_eq!(to_roman(3999), "MMMCMXCIX");
+        assert_eq!(NumberStyle::LowerRoman.format(12), "xii");
+    }
+
+    #[test]
+    fn roman_out_of_range() {
+        assert_eq!(to_roman(0), "0");
+        assert_eq!(to_roman(4000), "4000");
+        assert_eq!(NumberStyle::LowerRoman.format(4000), "4000");
+    }
+
+    #[test]
+    fn alpha() {
+        assert_eq!(to_alpha(0), "0");
+        assert_eq!(to_alpha(1), "A");
+        assert_eq!(to_alpha(26), "Z");
+        assert_eq!(to_alpha(27), "AA");
+        assert_eq!(to_alpha(28), "BB");
+        assert_eq!(to_alpha(52), "ZZ");
+        assert_eq!(to_alpha(53), "AAA");
+        assert_eq!(NumberStyle::LowerAlpha.format(27), "aa");
+    }
+
+    #[test]
+    fn kanji() {
+        assert_eq!(to_kanji(0), "〇");
+        assert_eq!(to_kanji(1), "一");
+        assert_eq!(to_kanji(10), "十");
+        assert_eq!(to_kanji(11), "十一");
+        assert_eq!(to_kanji(105), "百五");
+        assert_eq!(to_kanji(2024), "二千二十四");
+        assert_eq!(to_kanji(10000), "一万");
+        assert_eq!(to_kanji(100_000_001), "一億一");
+    }
+
+    #[test]
+    fn count_pages_continues_across_sections() {
+        let counters = count_pages(
+            &[(numbering(None, 0, false), 2), (numbering(None, 0, false), 2)],
+            &NumberStyle::Decimal,
+        );
+
+        assert_eq!(numbers(&counters), vec![Some(1), Some(2), Some(3), Some(4)]);
+        assert_eq!(counters[3].section_page, 2);
+        assert_eq!(counters[3].section_pages, 2);
+        assert!(!needs_page_labels(&counters));
+    }
+
+    #[test]
+    fn count_pages_start_offset_and_exclude() {
+        let counters = count_pages(
+            &[
+                (numbering(None, 0, true), 1),
+                (numbering(Some(1), 0, false), 2),
+                (numbering(None, 5, false), 1),
+                (numbering(Some(3), -10, false), 1),
+            ],
+            &NumberStyle::Decimal,
+        );
+
+        // NOTE: offset でカウンターが 1 未満になる場合は 1 にする
+        assert_eq!(numbers(&counters), vec![None, Some(1), Some(2), Some(8), Some(1)]);
+        assert_eq!(total_pages(&counters), 4);
+        assert!(needs_page_labels(&counters));
+    }
+
+    #[test]
+    fn count_pages_number_style() {
+        let mut roman = numbering(None, 0, false);
+        roman.number_style = Some(NumberStyle::LowerRoman);
+        let counters = count_pages(&[(roman, 1), (numbering(None, 0, false), 1)], &NumberStyle::Kanji);
+
+        assert_eq!(counters[0].number_style, NumberStyle::LowerRoman);
+        assert_eq!(counters[1].number_style, NumberStyle::Kanji);
+        assert!(needs_page_labels(&counters));
+    }
+
+    #[test]
+    fn count_pages_empty_section() {
+        let counters = count_pages(
+            &[(numbering(None, 0, false), 0), (numbering(None, 0, false), 1)],
+            &NumberStyle::Decimal,
+        );
+
+        assert_eq!(numbers(&counters), vec![Some(1)]);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::block_document::geometry::GeoSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "orientation")]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+// NOTE: Rust から Document を組み立てる場合の用紙サイズ（mm、縦向き）
+pub const A3: GeoSize = GeoSize { width: 297.0, height: 420.0 };
+pub const A4: GeoSize = GeoSize { width: 210.0, height: 297.0 };
+pub const A5: GeoSize = GeoSize { width: 148.0, height: 210.0 };
+pub const A6: GeoSize = GeoSize { width: 105.0, height: 148.0 };
+pub const B4: GeoSize = GeoSize { width: 257.0, height: 364.0 };
+pub const B5: GeoSize = GeoSize { width: 182.0, height: 257.0 };
+pub const B6: GeoSize = GeoSize { width: 128.0, height: 182.0 };
+pub const LETTER: GeoSize = GeoSize { width: 215.9, height: 279.4 };
+pub const LEGAL: GeoSize = GeoSize { width: 215.9, height: 355.6 };
+pub const TABLOID: GeoSize = GeoSize { width: 279.4, height: 431.8 };
+
+// NOTE: 名前付きの用紙サイズ（B 列は JIS）。スキーマの page_size の値もこの一覧から生成する
+pub const NAMED_PAGE_SIZES: &[(&str, GeoSize)] = &[
+    ("A3", A3),
+    ("A4", A4),
+    ("A5", A5),
+    ("A6", A6),
+    ("B4", B4),
+    ("B5", B5),
+    ("B6", B6),
+    ("Letter", LETTER),
+    ("Legal", LEGAL),
+    ("Tabloid", TABLOID),
+    ("長3", GeoSize { width: 120.0, height: 235.0 }),
+    ("長4", GeoSize { width: 90.0, height: 205.0 }),
+    ("角2", GeoSize { width: 240.0, height: 332.0 }),
+    ("洋長3", GeoSize { width: 120.0, height: 235.0 }),
+];
+
+pub fn named_page_size(name: &str) -> Option<GeoSize> {
+    NAMED_PAGE_SIZES
+        .iter()
+        .find(|(page_size_name, _)| *page_size_name == name)
+        .map(|(_, page_size)| page_size.clone())
+}
+
+// NOTE: 向きに合わせて幅と高さを入れ替える
+pub fn orient(size: &GeoSize, orientation: &Orientation) -> GeoSize {
+    let is_landscape = size.width > size.height;
+    match (orientation, is_landscape) {
+        (Orientation::Portrait, true) | (Orientation::Landscape, false) => {
+            GeoSize::new(size.height, size.width)
+        }
+        _ => size.clone(),
+    }
+}
+
+// NOTE: 用紙サイズと向きの指定からページサイズを決める（どちらも未指定の場合は None）
+pub fn resolve(page_size: Option<GeoSize>, orientation: Option<Orientation>, default_page_size: &GeoSize) -> Option<GeoSize> {
+    match (page_size, orientation) {
+        (Some(page_size), Some(orientation)) => Some(orient(&page_size, &orientation)),
+        (Some(page_size), None) => Some(page_size),
+        (None, Some(orientation)) => Some(orient(default_page_size, &orientation)),
+        (None, None) => None,
+    }
+}
@@ -1,60 +1,261 @@
 use crate::block_document::block::Block;
+use crate::block_document::data_uri;
+use crate::block_document::debug_overlay;
 use crate::block_document::direction::Direction;
 use crate::block_document::document::{Document as BlockDocument, DPI as BlockDPI};
-use crate::block_document::geometry::GeoRect;
+use crate::block_document::geometry::{GeoRect, GeoSize};
 use crate::block_document::image::Image as BlockImage;
 use crate::block_document::line::Line as BlockLine;
 use crate::block_document::page::Page;
-use crate::block_document::page_number::PageNumber;
+use crate::block_document::page_number::{
+    count_pages, needs_page_labels, total_pages, NumberStyle, PageCounter, PageNumber,
+    PageNumbering,
+};
 use crate::block_document::rectangle::Rectangle as BlockRectangle;
+use crate::block_document::reproducible;
+use crate::block_document::resource::{load_font_data, load_image};
 use crate::block_document::style::{
     BorderStyle, HorizontalAlignment, Style, TextOutlineStyle, TextStyle, VerticalAlignment,
 };
 use crate::block_document::text::Text as BlockText;
 use crate::block_document::text_renderer::measure_text;
+use crate::block_document::text_variable::{now, reproducible_date, TextVariables};
 use image::DynamicImage;
+use printpdf::lopdf::{Dictionary, Document as LoDocument, Object};
 use printpdf::{
-    Color, Image, ImageTransform, Line, LineDashPattern, Mm, PdfDocument, PdfDocumentReference,
-    PdfPageIndex, Point, Rect, Rgb, TextRenderingMode,
+    Color, Image, ImageTransform, IndirectFontRef, Line, LineDashPattern, Mm, PdfDocument,
+    PdfDocumentReference, PdfPageIndex, Point, Rect, Rgb, TextRenderingMode,
 };
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
-use std::io::BufWriter;
+use std::io::{self, BufWriter, Write};
+use std::process::exit;
+
+// NOTE: レイアウト済みのドキュメントを1つの PDF に連結して保存する（フォントは PDF 内で共有する）
+// NOTE: ページ番号やテキスト変数はドキュメントごとに計算済み
+// NOTE: is_reproducible の場合は同じ入力から同じバイト列の PDF を出力する（ID と日時を固定する）
+// NOTE: 書き込みのエラー（パイプが閉じられた場合など）は呼び出し側で処理する
+pub fn save_laid_out<W: Write>(
+    laid_out_documents: Vec<LaidOutDocument>,
+    writer: W,
+    is_debug: bool,
+    is_reproducible: bool,
+) -> io::Result<()> {
+    let Some(first_document) = laid_out_documents.first() else {
+        eprintln!("No documents to save.");
+        return Ok(());
+    };
+
+    let first_page_size = first_document
+        .pages
+        .first()
+        .and_then(|page| page.page_size())
+        .unwrap_or(&first_document.page_size);
+    let (mut doc, mut page_index, _) = PdfDocument::new(
+        first_document.title.clone(),
+        Mm(first_page_size.width),
+        Mm(first_page_size.height),
+        "Layer 1",
+    );
+    if is_reproducible {
+        let date = reproducible_date();
+        doc = doc
+            .with_creation_date(date)
+            .with_mod_date(date)
+            .with_metadata_date(date);
+    }
+    let fonts = PdfFonts::new();
+
+    // NOTE: 描画（frame が確定している）
+    let mut i = 0;
+    for laid_out_document in laid_out_documents.iter() {
+        for (j, page) in laid_out_document.pages.iter().enumerate() {
+            let page_size = page.page_size().unwrap_or(&laid_out_document.page_size);
+            let page_frame = page_frame(page_size);
+
+            if i > 0 {
+                (page_index, _) = doc.add_page(
+                    Mm(page_size.width),
+                    Mm(page_size.height),
+                    "Layer 1",
+                );
+            }
+
+            i += 1;
+
+            if is_debug {
+                draw_grid(&doc, &page_index, &page_frame)
+            }
+
+            for block in page.drawn_blocks() {
+                draw(
+                    &doc,
+                    &fonts,
+                    &page_index,
+                    &page_frame,
+                    &laid_out_document.font_path,
+                    block,
+                );
+            }
+
+            // NOTE: ページ番号を付与しないページ（表紙など）は描画しない
+            let page_counter = &laid_out_document.page_counters[j];
+            if let Some(page_number) = &laid_out_document.page_number
+                && page_counter.number.is_some()
+            {
+                draw_page_number(
+                    &doc,
+                    &fonts,
+                    &page_index,
+                    &page_frame,
+                    &laid_out_document.font_path,
+                    page_number,
+                    &laid_out_document.page_variables[j],
+                );
+            }
+
+            // NOTE: ブロックの上に重ねる（別のレイヤーなのでビューアで非表示にできる）
+            if is_debug {
+                debug_overlay::draw(
+                    &doc,
+                    &page_index,
+                    &page_frame,
+                    page,
+                    &fonts.get(&doc, &laid_out_document.font_path),
+                );
+            }
+        }
+    }
+
+    let page_counters: Vec<PageCounter> = laid_out_documents
+        .iter()
+        .flat_map(|laid_out_document| laid_out_document.page_counters.iter().cloned())
+        .collect();
+    let needs_page_labels = needs_page_labels(&page_counters);
+    let bytes = doc.save_to_bytes().unwrap();
+    let mut writer = BufWriter::new(writer);
+    if !needs_page_labels && !is_reproducible {
+        writer.write_all(&bytes)?;
+        return writer.flush();
+    }
+
+    // NOTE: printpdf で設定できない部分は lopdf で書き換える
+    let mut pdf = LoDocument::load_mem(&bytes).unwrap();
+    if needs_page_labels {
+        add_page_labels(&mut pdf, &page_counters);
+    }
+    if is_reproducible {
+        reproducible::apply(&mut pdf);
+    }
+
+    pdf.save_to(&mut writer)?;
+    writer.flush()
+}
+
+// NOTE: レイアウト済みのドキュメント
+pub struct LaidOutDocument {
+    pub title: String,
+    pub page_size: GeoSize,
+    pub font_path: String,
+    pub page_number: Option<PageNumber>,
+    pub pages: Vec<Page>,
+    pub page_counters: Vec<PageCounter>,
+    pub page_variables: Vec<TextVariables>,
+}
 
-const CURRENT_PAGE_NUMBER_PLACEHOLDER: &'static str = "${CURRENT_PAGE_NUMBER}";
-const TOTAL_PAGES_PLACEHOLDER: &'static str = "${TOTAL_PAGES}";
+// NOTE: ページ変数の幅を確保してレイアウトし直す回数の上限
+const MAX_RESERVE_LAYOUTS: usize = 3;
 
-pub fn save(block_document: BlockDocument, file: File, is_debug: bool) {
+// NOTE: レイアウト（frame を確定する）
+pub fn layout(block_document: &BlockDocument, is_reproducible: bool) -> LaidOutDocument {
     let mut working_block_document = block_document.clone();
 
-    let (doc, mut page_index, _) = PdfDocument::new(
-        working_block_document.title.clone(),
-        Mm(working_block_document.page_size.width),
-        Mm(working_block_document.page_size.height),
-        "Layer 1",
-    );
+    let document_variables = TextVariables::new(&working_block_document.title, &now(is_reproducible));
+    for page in working_block_document.pages.iter_mut() {
+        for block in page.blocks_mut() {
+            for text in block.texts_mut() {
+                text.replace_variables(&document_variables, &block_document.font_path);
+            }
+        }
+    }
 
-    // NOTE: 基準点は左下
-    let page_frame = GeoRect::new(
-        working_block_document.page_size.width,
-        working_block_document.page_size.height,
-        0.0,
-        0.0,
-    );
+    // NOTE: ページ変数の値はページ分割後に決まるため、1回目のレイアウトのページ番号で幅を確保してレイアウトし直す
+    // NOTE: （幅の確保でページ数が変わった場合は繰り返す）
+    let has_page_variables = working_block_document
+        .pages
+        .iter_mut()
+        .flat_map(|page| page.blocks_mut())
+        .flat_map(|block| block.texts_mut())
+        .any(|text| text.has_page_variables());
+    let (mut drawable_pages, mut page_counters) = layout_pages(&working_block_document);
+    for _ in 0..if has_page_variables { MAX_RESERVE_LAYOUTS } else { 0 } {
+        let reserved_variables = page_variables(&document_variables, &page_counters);
+        let mut reserved_block_document = working_block_document.clone();
+        for page in reserved_block_document.pages.iter_mut() {
+            for block in page.blocks_mut() {
+                for text in block.texts_mut() {
+                    text.reserve_variables(&reserved_variables, &block_document.font_path);
+                }
+            }
+        }
 
-    // NOTE: レイアウト（frame を確定する）
+        let (reserved_pages, reserved_page_counters) = layout_pages(&reserved_block_document);
+        let is_stable = reserved_page_counters == page_counters;
+        drawable_pages = reserved_pages;
+        page_counters = reserved_page_counters;
+        if is_stable {
+            break;
+        }
+    }
+
+    // NOTE: ページ番号などの変数を置き換える
+    let page_variables = page_variables(&document_variables, &page_counters);
+    for (page, variables) in drawable_pages.iter_mut().zip(page_variables.iter()) {
+        for block in page.blocks_mut() {
+            for text in block.texts_mut() {
+                text.replace_variables(variables, &block_document.font_path);
+            }
+        }
+    }
+
+    LaidOutDocument {
+        title: working_block_document.title,
+        page_size: working_block_document.page_size,
+        font_path: working_block_document.font_path,
+        page_number: working_block_document.page_number,
+        pages: drawable_pages,
+        page_counters,
+        page_variables,
+    }
+}
+
+// NOTE: ページ分割してページごとのページ番号を数える
+fn layout_pages(block_document: &BlockDocument) -> (Vec<Page>, Vec<PageCounter>) {
+    let mut working_block_document = block_document.clone();
     let mut drawable_pages: Vec<Page> = Vec::new();
+    let mut sections: Vec<(PageNumbering, usize)> = Vec::new();
     for page in working_block_document.pages.iter_mut() {
+        // NOTE: 余白を除いた領域に配置する
+        let page_frame = page_frame(
+            page.page_size()
+                .unwrap_or(&working_block_document.page_size),
+        )
+        .inset(page.margin().unwrap_or(&working_block_document.margin));
+
         match page {
             Page::DynamicPage(dynamic_page) => {
                 let applied_pages = dynamic_page.apply_constraints(
                     &page_frame,
                     &Direction::Vertical,
                     &working_block_document.font_path,
+                    drawable_pages.len() + 1,
                 );
 
+                sections.push((dynamic_page.page_numbering.clone(), applied_pages.len()));
+
                 for applied_page in applied_pages {
-                    drawable_pages.push(Page::DynamicPage(applied_page));
+                    drawable_pages.push(Page::DynamicPage(Box::new(applied_page)));
                 }
             }
             Page::StaticPage(static_page) => {
@@ -65,6 +266,8 @@ pub fn save(block_document: BlockDocument, file: File, is_debug: bool) {
                     static_page.auto_pagination,
                 );
 
+                sections.push((static_page.page_numbering.clone(), applied_pages.len()));
+
                 for applied_page in applied_pages {
                     drawable_pages.push(Page::StaticPage(applied_page));
                 }
@@ -72,105 +275,139 @@ pub fn save(block_document: BlockDocument, file: File, is_debug: bool) {
         }
     }
 
-    let total_pages = drawable_pages.len();
+    let default_number_style = block_document
+        .page_number
+        .as_ref()
+        .map(|page_number| page_number.number_style.clone())
+        .unwrap_or_default();
+    let page_counters = count_pages(&sections, &default_number_style);
 
-    // NOTE: 描画（frame が確定している）
-    let mut i = 0;
-    for page in drawable_pages.iter() {
-        match page {
-            Page::DynamicPage(dynamic_page) => {
-                if i > 0 {
-                    (page_index, _) = doc.add_page(
-                        Mm(working_block_document.page_size.width),
-                        Mm(working_block_document.page_size.height),
-                        "Layer 1",
-                    );
-                }
+    (drawable_pages, page_counters)
+}
 
-                i += 1;
+fn page_variables(document_variables: &TextVariables, page_counters: &[PageCounter]) -> Vec<TextVariables> {
+    let total_pages = total_pages(page_counters);
 
-                if is_debug {
-                    draw_grid(&doc, &page_index, &page_frame)
-                }
+    page_counters
+        .iter()
+        .map(|page_counter| document_variables.with_page(page_counter, total_pages))
+        .collect()
+}
 
-                for block in dynamic_page.common_blocks.iter() {
-                    draw(
-                        &doc,
-                        &page_index,
-                        &page_frame,
-                        &working_block_document.font_path,
-                        block,
-                    );
-                }
+// NOTE: PDF に追加したフォントを再利用する（テキストごとにフォントを埋め込まないように）
+struct PdfFonts {
+    fonts: RefCell<HashMap<String, IndirectFontRef>>,
+}
 
-                for block in dynamic_page.content_blocks.iter() {
-                    draw(
-                        &doc,
-                        &page_index,
-                        &page_frame,
-                        &working_block_document.font_path,
-                        block,
-                    );
-                }
+impl PdfFonts {
+    fn new() -> PdfFonts {
+        PdfFonts {
+            fonts: RefCell::new(HashMap::new()),
+        }
+    }
 
-                if let Some(page_number) = &block_document.page_number {
-                    draw_page_number(
-                        &doc,
-                        &page_index,
-                        &page_frame,
-                        &working_block_document.font_path,
-                        page_number,
-                        i,
-                        total_pages,
-                    );
-                }
+    fn get(&self, doc: &PdfDocumentReference, font_path: &str) -> IndirectFontRef {
+        if let Some(font) = self.fonts.borrow().get(font_path) {
+            return font.clone();
+        }
+
+        let font_data = match load_font_data(font_path) {
+            Some(font_data) => font_data,
+            None => {
+                eprintln!("Failed to open font file: {}.", data_uri::display(font_path));
+                exit(1);
             }
-            Page::StaticPage(static_page) => {
-                if i > 0 {
-                    (page_index, _) = doc.add_page(
-                        Mm(working_block_document.page_size.width),
-                        Mm(working_block_document.page_size.height),
-                        "Layer 1",
-                    );
-                }
+        };
+        let font = doc.add_external_font(font_data.as_slice()).unwrap();
+        self.fonts
+            .borrow_mut()
+            .insert(font_path.to_string(), font.clone());
 
-                i += 1;
+        font
+    }
+}
 
-                if is_debug {
-                    draw_grid(&doc, &page_index, &page_frame)
-                }
+pub fn page_frame(page_size: &GeoSize) -> GeoRect {
+    GeoRect::new(page_size.width, page_size.height, 0.0, 0.0)
+}
 
-                for block in static_page.blocks.iter() {
-                    draw(
-                        &doc,
-                        &page_index,
-                        &page_frame,
-                        &working_block_document.font_path,
-                        block,
-                    );
-                }
+// NOTE: ビューアのページ番号表示を合わせるため PageLabels を追加する
+fn add_page_labels(pdf: &mut LoDocument, page_counters: &[PageCounter]) {
+    let mut nums: Vec<Object> = Vec::new();
+    let mut previous: Option<&PageCounter> = None;
+    for (i, counter) in page_counters.iter().enumerate() {
+        let is_continued = match (previous, counter.number) {
+            (Some(previous), Some(number)) => {
+                previous.number.map(|n| n + 1) == Some(number)
+                    && previous.number_style == counter.number_style
             }
+            (Some(previous), None) => previous.number.is_none(),
+            (None, _) => false,
+        };
+        previous = Some(counter);
+
+        if is_continued {
+            continue;
         }
+
+        let mut label = Dictionary::new();
+        if let Some(number) = counter.number {
+            label.set(
+                "S",
+                Object::Name(
+                    match counter.number_style {
+                        NumberStyle::Decimal | NumberStyle::Kanji => "D",
+                        NumberStyle::LowerRoman => "r",
+                        NumberStyle::UpperRoman => "R",
+                        NumberStyle::LowerAlpha => "a",
+                        NumberStyle::UpperAlpha => "A",
+                    }
+                    .into(),
+                ),
+            );
+            label.set("St", Object::Integer(number as i64));
+        }
+
+        nums.push(Object::Integer(i as i64));
+        nums.push(Object::Dictionary(label));
     }
 
-    doc.save(&mut BufWriter::new(file)).unwrap();
+    let mut page_labels = Dictionary::new();
+    page_labels.set("Nums", Object::Array(nums));
+    pdf.catalog_mut()
+        .unwrap()
+        .set("PageLabels", Object::Dictionary(page_labels));
 }
 
 fn draw_page_number(
     doc: &PdfDocumentReference,
+    fonts: &PdfFonts,
     page_index: &PdfPageIndex,
     parent_frame: &GeoRect,
     font_path: &String,
     page_number: &PageNumber,
-    current_page_number: usize,
-    total_pages: usize,
+    variables: &TextVariables,
 ) {
-    let text = format!(
-        "{}",
-        page_number.format
-            .replace(CURRENT_PAGE_NUMBER_PLACEHOLDER, current_page_number.to_string().as_str())
-            .replace(TOTAL_PAGES_PLACEHOLDER, total_pages.to_string().as_str()),
+    let block_text = page_number_text(parent_frame, font_path, page_number, variables);
+
+    draw_text(
+        doc,
+        fonts,
+        page_index,
+        font_path,
+        &block_text,
+        parent_frame,
     );
+}
+
+// NOTE: ページ番号を描画するテキスト（frame 未指定の場合はページ下部の中央）
+pub fn page_number_text(
+    parent_frame: &GeoRect,
+    font_path: &String,
+    page_number: &PageNumber,
+    variables: &TextVariables,
+) -> BlockText {
+    let text = variables.replace(&page_number.format);
 
     let mut block_text = BlockText::new(
         text,
@@ -194,18 +431,13 @@ fn draw_page_number(
         ));
     }
 
-    draw_text(
-        doc,
-        page_index,
-        font_path,
-        &block_text,
-        parent_frame,
-    );
+    block_text
 }
 
 // NOTE: parent_frame の基準点は左下
 fn draw(
     doc: &PdfDocumentReference,
+    fonts: &PdfFonts,
     page_index: &PdfPageIndex,
     parent_frame: &GeoRect,
     font_path: &String,
@@ -219,7 +451,7 @@ fn draw(
                 .unwrap_or(&GeoRect::none())
                 .transform(parent_frame);
             for block in block_container.blocks.iter() {
-                draw(doc, page_index, &lb_frame, font_path, block);
+                draw(doc, fonts, page_index, &lb_frame, font_path, block);
             }
         }
         Block::Wrapper(block_wrapper) => {
@@ -229,7 +461,7 @@ fn draw(
                 .unwrap_or(&GeoRect::none())
                 .transform(parent_frame);
 
-            draw(doc, page_index, &lb_frame, font_path, &block_wrapper.block);
+            draw(doc, fonts, page_index, &lb_frame, font_path, &block_wrapper.block);
         }
         Block::Flexible(flexible_container) => {
             let lb_frame = flexible_container
@@ -238,7 +470,7 @@ fn draw(
                 .unwrap_or(&GeoRect::none())
                 .transform(parent_frame);
             for block in flexible_container.blocks.iter() {
-                draw(doc, page_index, &lb_frame, font_path, block);
+                draw(doc, fonts, page_index, &lb_frame, font_path, block);
             }
         }
         Block::FlexibleItem(flexible_item) => {
@@ -248,12 +480,12 @@ fn draw(
                 .unwrap_or(&GeoRect::none())
                 .transform(parent_frame);
 
-            draw(doc, page_index, &lb_frame, font_path, &flexible_item.block);
+            draw(doc, fonts, page_index, &lb_frame, font_path, &flexible_item.block);
         }
         Block::Line(line) => draw_line(doc, page_index, line, parent_frame),
         Block::Rectangle(rectangle) => draw_rectangle(doc, page_index, rectangle, parent_frame),
         Block::Text(text) => {
-            draw_text(doc, page_index, font_path, text, parent_frame);
+            draw_text(doc, fonts, page_index, font_path, text, parent_frame);
         }
         Block::Image(image) => {
             draw_image(doc, page_index, image, parent_frame);
@@ -415,6 +647,7 @@ fn draw_line(
 
 fn draw_text(
     doc: &PdfDocumentReference,
+    fonts: &PdfFonts,
     page_index: &PdfPageIndex,
     font_path: &String,
     block_text: &BlockText,
@@ -427,21 +660,9 @@ fn draw_text(
             let layer1 = doc.get_page(*page_index).add_layer("Layer 1");
 
             let mut border_required = false;
-            let mut h_alignment: Option<&HorizontalAlignment> = None;
-            let mut v_alignment: Option<&VerticalAlignment> = None;
 
             for style in &block_text.styles {
                 match style {
-                    Style::Alignment(alignment) => {
-                        match alignment.horizontal.as_ref() {
-                            Some(h_a) => h_alignment = Some(h_a),
-                            _ => {}
-                        }
-                        match alignment.vertical.as_ref() {
-                            Some(v_a) => v_alignment = Some(v_a),
-                            _ => {}
-                        }
-                    }
                     Style::BorderColor(rgb_color) => {
                         border_required = true;
                         layer1.set_outline_color(Color::Rgb(Rgb {
@@ -469,52 +690,7 @@ fn draw_text(
                 }
             }
 
-            let x_offset = match h_alignment {
-                Some(h_a) => match h_a {
-                    HorizontalAlignment::Left => 0.0,
-                    HorizontalAlignment::Center => {
-                        (geo_frame.width()
-                            - block_text
-                                .frame
-                                .as_ref()
-                                .unwrap_or(&GeoRect::zero())
-                                .width())
-                            / 2.0
-                    }
-                    HorizontalAlignment::Right => {
-                        geo_frame.width()
-                            - block_text
-                                .frame
-                                .as_ref()
-                                .unwrap_or(&GeoRect::zero())
-                                .width()
-                    }
-                },
-                _ => 0.0,
-            };
-            let y_offset = match v_alignment {
-                Some(v_a) => match v_a {
-                    VerticalAlignment::Top => 0.0,
-                    VerticalAlignment::Center => {
-                        (geo_frame.height()
-                            - block_text
-                                .frame
-                                .as_ref()
-                                .unwrap_or(&GeoRect::zero())
-                                .height())
-                            / 2.0
-                    }
-                    VerticalAlignment::Bottom => {
-                        geo_frame.height()
-                            - block_text
-                                .frame
-                                .as_ref()
-                                .unwrap_or(&GeoRect::zero())
-                                .height()
-                    }
-                },
-                _ => 0.0,
-            };
+            let (x_offset, y_offset) = text_offset(block_text, geo_frame);
 
             if border_required {
                 layer1.add_line(Line {
@@ -596,11 +772,7 @@ fn draw_text(
                 }
             }
 
-            let font = doc
-                .add_external_font(
-                    File::open(block_text.font_path.as_ref().unwrap_or(font_path)).unwrap(),
-                )
-                .unwrap();
+            let font = fonts.get(doc, block_text.font_path.as_ref().unwrap_or(font_path));
 
             // // NOTE: 改行を考慮無し
             // if !block_text.text.contains("\n") {
@@ -632,61 +804,100 @@ fn draw_text(
 
             // NOTE: 折り返し結果を使用してテキストを描画
             let display_lines = block_text.get_display_text();
+            let baselines = text_baselines(block_text, &lb_frame, y_offset);
 
-            if display_lines.len() == 1 && !display_lines[0].contains('\n') {
-                // NOTE: 単一行の場合
+            for (line, y) in display_lines.into_iter().zip(baselines) {
                 layer2.use_text(
-                    display_lines[0].clone(),
+                    line,
                     block_text.font_size,
                     Mm(lb_frame.min_x() + x_offset),
-                    Mm(lb_frame.min_y() - y_offset),
+                    Mm(y),
                     &font,
                 );
-            } else {
-                // NOTE: 複数行の場合（折り返し結果または元々の改行）
-                let line_height = if let Some(wrapped) = block_text.get_wrapped_text() {
-                    // NOTE: 折り返し結果の行の高さを使用
-                    if !wrapped.lines.is_empty() {
-                        wrapped.total_size.height / wrapped.lines.len() as f32
-                    } else {
-                        lb_frame.height() / display_lines.len() as f32
-                    }
-                } else {
-                    // NOTE: 従来の計算
-                    lb_frame.height() / display_lines.len() as f32
-                };
-
-                let mut current_y = lb_frame.max_y() - line_height - y_offset;
-
-                for line in display_lines {
-                    layer2.use_text(
-                        line,
-                        block_text.font_size,
-                        Mm(lb_frame.min_x() + x_offset),
-                        Mm(current_y),
-                        &font,
-                    );
-                    current_y -= line_height;
-                }
             }
         }
     }
 }
 
+// NOTE: 揃え（alignment）による描画位置のずれ（親の frame を基準にする）
+pub fn text_offset(block_text: &BlockText, geo_frame: &GeoRect) -> (f32, f32) {
+    let mut h_alignment: Option<&HorizontalAlignment> = None;
+    let mut v_alignment: Option<&VerticalAlignment> = None;
+
+    for style in &block_text.styles {
+        if let Style::Alignment(alignment) = style {
+            if let Some(h_a) = alignment.horizontal.as_ref() {
+                h_alignment = Some(h_a);
+            }
+            if let Some(v_a) = alignment.vertical.as_ref() {
+                v_alignment = Some(v_a);
+            }
+        }
+    }
+
+    let frame = block_text.frame.as_ref().unwrap_or(&GeoRect::zero()).clone();
+    let x_offset = match h_alignment {
+        Some(HorizontalAlignment::Center) => (geo_frame.width() - frame.width()) / 2.0,
+        Some(HorizontalAlignment::Right) => geo_frame.width() - frame.width(),
+        Some(HorizontalAlignment::Left) | None => 0.0,
+    };
+    let y_offset = match v_alignment {
+        Some(VerticalAlignment::Center) => (geo_frame.height() - frame.height()) / 2.0,
+        Some(VerticalAlignment::Bottom) => geo_frame.height() - frame.height(),
+        Some(VerticalAlignment::Top) | None => 0.0,
+    };
+
+    (x_offset, y_offset)
+}
+
+// NOTE: 各行を描画する Y 座標（ベースライン、左下基準）
+pub fn text_baselines(block_text: &BlockText, lb_frame: &GeoRect, y_offset: f32) -> Vec<f32> {
+    let display_lines = block_text.get_display_text();
+
+    if display_lines.len() == 1 && !display_lines[0].contains('\n') {
+        // NOTE: 単一行の場合
+        return vec![lb_frame.min_y() - y_offset];
+    }
+
+    // NOTE: 複数行の場合（折り返し結果または元々の改行）
+    let line_height = if let Some(wrapped) = block_text.get_wrapped_text() {
+        // NOTE: 折り返し結果の行の高さを使用
+        if !wrapped.lines.is_empty() {
+            wrapped.total_size.height / wrapped.lines.len() as f32
+        } else {
+            lb_frame.height() / display_lines.len() as f32
+        }
+    } else {
+        // NOTE: 従来の計算
+        lb_frame.height() / display_lines.len() as f32
+    };
+
+    let mut baselines: Vec<f32> = Vec::new();
+    let mut current_y = lb_frame.max_y() - line_height - y_offset;
+    for _ in display_lines.iter() {
+        baselines.push(current_y);
+        current_y -= line_height;
+    }
+
+    baselines
+}
+
 fn draw_image(
     doc: &PdfDocumentReference,
     page_index: &PdfPageIndex,
     block_image: &BlockImage,
     geo_frame: &GeoRect,
 ) {
-    if !fs::exists(&block_image.path)
-        .map_err(|e| {
-            eprintln!(
-                "Failed to check if file {} exists due to {}",
-                block_image.path, e
-            );
-        })
-        .unwrap_or(false)
+    // NOTE: data URI の場合はファイルの存在確認をしない
+    if !data_uri::is_data_uri(&block_image.path)
+        && !fs::exists(&block_image.path)
+            .map_err(|e| {
+                eprintln!(
+                    "Failed to check if file {} exists due to {}",
+                    block_image.path, e
+                );
+            })
+            .unwrap_or(false)
     {
         eprintln!("No such file or directory -> {:?}", &block_image.path);
         return;
@@ -696,12 +907,9 @@ fn draw_image(
         if frame.point.is_some() {
             let lb_frame = frame.transform(geo_frame);
 
-            let image = image::io::Reader::open(&block_image.path)
-                .unwrap()
-                .decode()
-                .unwrap();
+            let image = load_image(&block_image.path).unwrap();
 
-            let rgb_image = ensure_rgb_format(image);
+            let rgb_image = ensure_rgb_format(image.as_ref().clone());
 
             let pdf_image = Image::from_dynamic_image(&rgb_image);
 
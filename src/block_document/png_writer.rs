@@ -0,0 +1,458 @@
+use crate::block_document::block::Block;
+use crate::block_document::data_uri;
+use crate::block_document::document::DPI as BlockDPI;
+use crate::block_document::geometry::GeoRect;
+use crate::block_document::image::Image as BlockImage;
+use crate::block_document::line::Line as BlockLine;
+use crate::block_document::pdf_writer::{
+    page_frame, page_number_text, text_baselines, text_offset, LaidOutDocument,
+};
+use crate::block_document::rectangle::Rectangle as BlockRectangle;
+use crate::block_document::resource::{load_font, load_image};
+use crate::block_document::style::{BorderStyle, RgbColor, Style, TextStyle};
+use crate::block_document::text::Text as BlockText;
+use ab_glyph::{point, Font, FontArc, PxScale, ScaleFont};
+use image::codecs::png::PngEncoder;
+use image::imageops::{self, FilterType};
+use image::{ColorType, ImageEncoder, ImageError, Rgb, RgbImage};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::exit;
+
+// NOTE: 画面表示用のサムネイルを想定した既定の解像度
+pub const DEFAULT_DPI: f32 = 96.0;
+
+// NOTE: 印刷用の解像度の上限（A4 で約 3,500 万ピクセル）
+pub const MAX_DPI: f32 = 600.0;
+
+// NOTE: 1ページの画像の最大ピクセル数（RGB で約 300MB）。大きな用紙で画像がメモリを使い切らないように
+pub const MAX_PIXELS: u64 = 100_000_000;
+
+// NOTE: PDF の線の太さ・破線の長さ（pt）を mm に変換する
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+const WHITE: Rgb<u8> = Rgb([255, 255, 255]);
+const BLACK: Rgb<u8> = Rgb([0, 0, 0]);
+
+// NOTE: 描画する前にすべてのページの画像の大きさを確認する
+pub fn check_pixels(laid_out_documents: &[LaidOutDocument], dpi: f32) -> Result<(), String> {
+    let page_sizes = laid_out_documents.iter().flat_map(|laid_out_document| {
+        laid_out_document
+            .pages
+            .iter()
+            .map(|page| page.page_size().unwrap_or(&laid_out_document.page_size))
+    });
+
+    for (i, page_size) in page_sizes.enumerate() {
+        let (width, height) = image_size(page_size.width, page_size.height, dpi);
+        if width as u64 * height as u64 > MAX_PIXELS {
+            return Err(format!(
+                "Page {} would be {} x {} pixels at {} DPI (the maximum is {} pixels). Lower --dpi.",
+                i + 1,
+                width,
+                height,
+                dpi,
+                MAX_PIXELS
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// NOTE: width / height は mm
+fn image_size(width: f32, height: f32, dpi: f32) -> (u32, u32) {
+    let px_per_mm = dpi / 25.4;
+
+    (
+        (width * px_per_mm).round().max(1.0) as u32,
+        (height * px_per_mm).round().max(1.0) as u32,
+    )
+}
+
+// NOTE: レイアウト済みのドキュメントを PNG 用の画像にする（1ページ1枚）
+// NOTE: pdf_writer と同じ Block の木を描画する（PDF の既定値に合わせて、色の指定がない場合は黒、線の太さは 1pt）
+pub fn render(laid_out_documents: &[LaidOutDocument], dpi: f32) -> Vec<RgbImage> {
+    let mut images: Vec<RgbImage> = Vec::new();
+
+    for laid_out_document in laid_out_documents.iter() {
+        for (i, page) in laid_out_document.pages.iter().enumerate() {
+            let page_size = page.page_size().unwrap_or(&laid_out_document.page_size);
+            let page_frame = page_frame(page_size);
+            let mut canvas = Canvas::new(page_size.width, page_size.height, dpi);
+
+            for block in page.drawn_blocks() {
+                draw(&mut canvas, &page_frame, &laid_out_document.font_path, block);
+            }
+
+            // NOTE: ページ番号を付与しないページ（表紙など）は描画しない
+            if let Some(page_number) = &laid_out_document.page_number
+                && laid_out_document.page_counters[i].number.is_some()
+            {
+                let block_text = page_number_text(
+                    &page_frame,
+                    &laid_out_document.font_path,
+                    page_number,
+                    &laid_out_document.page_variables[i],
+                );
+                draw_text(&mut canvas, &laid_out_document.font_path, &block_text, &page_frame);
+            }
+
+            images.push(canvas.image);
+        }
+    }
+
+    images
+}
+
+// NOTE: 書き込みのエラー（パイプが閉じられた場合など）は呼び出し側で処理する
+pub fn write<W: Write>(image: &RgbImage, mut writer: W) -> io::Result<()> {
+    PngEncoder::new(&mut writer)
+        .write_image(image.as_raw(), image.width(), image.height(), ColorType::Rgb8)
+        .map_err(|e| match e {
+            ImageError::IoError(e) => e,
+            e => io::Error::other(e),
+        })?;
+
+    writer.flush()
+}
+
+// NOTE: 複数ページの場合はページ番号を付けたファイルに保存する（例: invoice.png → invoice-1.png, invoice-2.png）
+pub fn page_paths(output: &str, page_count: usize) -> Vec<String> {
+    if page_count == 1 {
+        return vec![output.to_string()];
+    }
+
+    let path = Path::new(output);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("png");
+
+    (1..=page_count)
+        .map(|i| {
+            path.with_file_name(format!("{}-{}.{}", stem, i, extension))
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+// NOTE: 座標は pdf_writer と同じく mm（左下基準）で受け取り、ピクセル（左上基準）に変換する
+struct Canvas {
+    image: RgbImage,
+    dpi: f32,
+    page_height: f32, // NOTE: mm
+}
+
+// NOTE: 線の色・太さ（mm）・破線の長さ（mm）
+struct Stroke {
+    color: Rgb<u8>,
+    width: f32,
+    dash: Option<f32>,
+}
+
+impl Canvas {
+    fn new(width: f32, height: f32, dpi: f32) -> Canvas {
+        let (image_width, image_height) = image_size(width, height, dpi);
+        Canvas {
+            image: RgbImage::from_pixel(image_width, image_height, WHITE),
+            dpi,
+            page_height: height,
+        }
+    }
+
+    fn px_per_mm(&self) -> f32 {
+        self.dpi / 25.4
+    }
+
+    fn x(&self, x: f32) -> f32 {
+        x * self.px_per_mm()
+    }
+
+    fn y(&self, y: f32) -> f32 {
+        (self.page_height - y) * self.px_per_mm()
+    }
+
+    // NOTE: coverage は 0.0〜1.0（アンチエイリアス用）
+    fn blend(&mut self, x: i64, y: i64, color: Rgb<u8>, coverage: f32) {
+        if x < 0 || y < 0 || x >= self.image.width() as i64 || y >= self.image.height() as i64 {
+            return;
+        }
+
+        let coverage = coverage.clamp(0.0, 1.0);
+        let pixel = self.image.get_pixel_mut(x as u32, y as u32);
+        for (channel, source) in pixel.0.iter_mut().zip(color.0) {
+            *channel = (*channel as f32 * (1.0 - coverage) + source as f32 * coverage).round() as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, min_x: f32, min_y: f32, max_x: f32, max_y: f32, color: Rgb<u8>) {
+        let (left, right) = (self.x(min_x), self.x(max_x));
+        let (top, bottom) = (self.y(max_y), self.y(min_y));
+        if right <= left || bottom <= top {
+            return;
+        }
+
+        for y in top.floor() as i64..bottom.ceil() as i64 {
+            let coverage_y = coverage(y, top, bottom);
+            for x in left.floor() as i64..right.ceil() as i64 {
+                self.blend(x, y, color, coverage(x, left, right) * coverage_y);
+            }
+        }
+    }
+
+    // NOTE: 水平・垂直の線のみ（Block の線や枠はいずれか）
+    fn stroke_line(&mut self, from: (f32, f32), to: (f32, f32), stroke: &Stroke) {
+        // NOTE: 細すぎる線が消えないように 1px 以上にする（PDF の 0pt の線と同じ扱い）
+        let half = stroke.width.max(1.0 / self.px_per_mm()) / 2.0;
+        let is_horizontal = from.1 == to.1;
+        let (start, end) = if is_horizontal {
+            (from.0.min(to.0), from.0.max(to.0))
+        } else {
+            (from.1.min(to.1), from.1.max(to.1))
+        };
+
+        let mut segments: Vec<(f32, f32)> = Vec::new();
+        match stroke.dash {
+            Some(dash) if dash > 0.0 => {
+                let mut position = start;
+                while position < end {
+                    segments.push((position, (position + dash).min(end)));
+                    position += dash * 2.0;
+                }
+            }
+            _ => segments.push((start, end)),
+        }
+
+        for (segment_start, segment_end) in segments {
+            if is_horizontal {
+                self.fill_rect(segment_start, from.1 - half, segment_end, from.1 + half, stroke.color);
+            } else {
+                self.fill_rect(from.0 - half, segment_start, from.0 + half, segment_end, stroke.color);
+            }
+        }
+    }
+
+    // NOTE: 角が欠けないように水平の線を太さの半分だけ延ばす
+    fn stroke_rect(&mut self, frame: &GeoRect, stroke: &Stroke) {
+        let half = stroke.width / 2.0;
+        let (min_x, min_y, max_x, max_y) = (frame.min_x(), frame.min_y(), frame.max_x(), frame.max_y());
+
+        self.stroke_line((min_x - half, min_y), (max_x + half, min_y), stroke);
+        self.stroke_line((min_x - half, max_y), (max_x + half, max_y), stroke);
+        self.stroke_line((min_x, min_y), (min_x, max_y), stroke);
+        self.stroke_line((max_x, min_y), (max_x, max_y), stroke);
+    }
+
+    // NOTE: (x, y) はベースラインの左端（PDF と同じくカーニングは考慮しない）
+    fn draw_text(&mut self, font: &FontArc, font_size: f32, text: &str, x: f32, y: f32, color: Rgb<u8>) {
+        // NOTE: font_size は pt（pt_to_px_scale は 96 DPI が基準）
+        let scale: PxScale = font.pt_to_px_scale(font_size * self.dpi / 96.0).unwrap();
+        let scaled_font = font.as_scaled(scale);
+
+        let mut x_px = self.x(x);
+        let y_px = self.y(y);
+        for c in text.chars() {
+            if c.is_control() {
+                continue;
+            }
+
+            let glyph_id = scaled_font.glyph_id(c);
+            let glyph = glyph_id.with_scale_and_position(scale, point(x_px, y_px));
+            x_px += scaled_font.h_advance(glyph_id);
+
+            if let Some(outlined_glyph) = font.outline_glyph(glyph) {
+                let bounds = outlined_glyph.px_bounds();
+                outlined_glyph.draw(|gx, gy, coverage| {
+                    self.blend(
+                        bounds.min.x as i64 + gx as i64,
+                        bounds.min.y as i64 + gy as i64,
+                        color,
+                        coverage,
+                    );
+                });
+            }
+        }
+    }
+}
+
+// NOTE: ピクセル [p, p + 1) のうち [start, end) に含まれる割合
+fn coverage(p: i64, start: f32, end: f32) -> f32 {
+    ((p + 1) as f32).min(end) - (p as f32).max(start)
+}
+
+fn rgb(rgb_color: &RgbColor) -> Rgb<u8> {
+    Rgb([rgb_color.r, rgb_color.g, rgb_color.b])
+}
+
+// NOTE: 枠線のスタイル（border_* のいずれかが指定されている場合のみ枠線を描画する）
+fn border(styles: &[Style]) -> (Stroke, bool) {
+    let mut stroke = Stroke {
+        color: BLACK,
+        width: PT_TO_MM,
+        dash: None,
+    };
+    let mut border_required = false;
+
+    for style in styles {
+        match style {
+            Style::BorderColor(rgb_color) => {
+                border_required = true;
+                stroke.color = rgb(rgb_color);
+            }
+            Style::BorderWidth(width) => {
+                border_required = true;
+                stroke.width = width * PT_TO_MM;
+            }
+            Style::BorderStyle(BorderStyle::Dash(i)) => {
+                border_required = true;
+                stroke.dash = Some(*i as f32 * PT_TO_MM);
+            }
+            _ => {}
+        }
+    }
+
+    (stroke, border_required)
+}
+
+// NOTE: parent_frame の基準点は左下（pdf_writer::draw と同じ）
+fn draw(canvas: &mut Canvas, parent_frame: &GeoRect, font_path: &String, block: &Block) {
+    match block {
+        Block::Line(line) => draw_line(canvas, line, parent_frame),
+        Block::Rectangle(rectangle) => draw_rectangle(canvas, rectangle, parent_frame),
+        Block::Text(text) => draw_text(canvas, font_path, text, parent_frame),
+        Block::Image(image) => draw_image(canvas, image, parent_frame),
+        Block::Container(_) | Block::Flexible(_) | Block::FlexibleItem(_) | Block::Wrapper(_) => {
+            let lb_frame = block.frame().unwrap_or(&GeoRect::none()).transform(parent_frame);
+            for child in block.children() {
+                draw(canvas, &lb_frame, font_path, child);
+            }
+        }
+    }
+}
+
+fn draw_rectangle(canvas: &mut Canvas, block_rectangle: &BlockRectangle, geo_frame: &GeoRect) {
+    let Some(frame) = block_rectangle.frame.as_ref().filter(|frame| frame.point.is_some()) else {
+        return;
+    };
+    let lb_frame = frame.transform(geo_frame);
+
+    let background_color = block_rectangle
+        .styles
+        .iter()
+        .rev()
+        .find_map(|style| match style {
+            Style::BackgroundColor(rgb_color) => Some(rgb(rgb_color)),
+            _ => None,
+        })
+        .unwrap_or(BLACK);
+    canvas.fill_rect(
+        lb_frame.min_x(),
+        lb_frame.min_y(),
+        lb_frame.max_x(),
+        lb_frame.max_y(),
+        background_color,
+    );
+
+    let (stroke, border_required) = border(&block_rectangle.styles);
+    if border_required {
+        canvas.stroke_rect(&lb_frame, &stroke);
+    }
+}
+
+fn draw_line(canvas: &mut Canvas, block_line: &BlockLine, geo_frame: &GeoRect) {
+    let lb_frame = block_line.frame.transform(geo_frame);
+    let (stroke, _) = border(&block_line.styles);
+
+    if lb_frame.min_x() == lb_frame.max_x() {
+        canvas.stroke_line(
+            (lb_frame.max_x(), lb_frame.min_y()),
+            (lb_frame.max_x(), lb_frame.max_y()),
+            &stroke,
+        );
+    } else {
+        canvas.stroke_line(
+            (lb_frame.min_x(), lb_frame.max_y()),
+            (lb_frame.max_x(), lb_frame.max_y()),
+            &stroke,
+        );
+    }
+}
+
+// NOTE: text_style: stroke の場合も輪郭ではなく text_outline_color で塗りつぶす（プレビュー用の簡略化）
+fn draw_text(canvas: &mut Canvas, font_path: &String, block_text: &BlockText, geo_frame: &GeoRect) {
+    let Some(frame) = block_text.frame.as_ref().filter(|frame| frame.point.is_some()) else {
+        return;
+    };
+    let lb_frame = frame.transform(geo_frame);
+    let (x_offset, y_offset) = text_offset(block_text, geo_frame);
+
+    let (stroke, border_required) = border(&block_text.styles);
+    if border_required {
+        canvas.stroke_rect(
+            &GeoRect::new(
+                lb_frame.width(),
+                lb_frame.height(),
+                lb_frame.min_x() + x_offset,
+                lb_frame.min_y() - y_offset,
+            ),
+            &stroke,
+        );
+    }
+
+    let mut fill_color = BLACK;
+    let mut outline_color = BLACK;
+    let mut is_stroke = false;
+    for style in &block_text.styles {
+        match style {
+            Style::TextFillColor(rgb_color) => fill_color = rgb(rgb_color),
+            Style::TextOutlineColor(rgb_color) => outline_color = rgb(rgb_color),
+            Style::TextStyle(text_style) => is_stroke = matches!(text_style, TextStyle::Stroke),
+            _ => {}
+        }
+    }
+    let color = if is_stroke { outline_color } else { fill_color };
+
+    let use_font_path = block_text.font_path.as_ref().unwrap_or(font_path);
+    let font = match load_font(use_font_path) {
+        Some(font) => font,
+        None => {
+            eprintln!("Failed to open font file: {}.", data_uri::display(use_font_path));
+            exit(1);
+        }
+    };
+
+    let display_lines = block_text.get_display_text();
+    let baselines = text_baselines(block_text, &lb_frame, y_offset);
+    for (line, y) in display_lines.iter().zip(baselines) {
+        canvas.draw_text(&font, block_text.font_size, line, lb_frame.min_x() + x_offset, y, color);
+    }
+}
+
+// NOTE: PDF と同じく画像のピクセル数と document::DPI から大きさを決める（frame の大きさには合わせない）
+fn draw_image(canvas: &mut Canvas, block_image: &BlockImage, geo_frame: &GeoRect) {
+    let Some(frame) = block_image.frame.as_ref().filter(|frame| frame.point.is_some()) else {
+        return;
+    };
+    let lb_frame = frame.transform(geo_frame);
+
+    let image = match load_image(&block_image.path) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("Failed to load image {}: {}", data_uri::display(&block_image.path), e);
+            return;
+        }
+    };
+
+    // NOTE: PDF ではアルファチャンネルを削除しているので合わせる
+    let width = (image.width() as f32 * canvas.dpi / BlockDPI).round().max(1.0) as u32;
+    let height = (image.height() as f32 * canvas.dpi / BlockDPI).round().max(1.0) as u32;
+    let resized = imageops::resize(&image.to_rgb8(), width, height, FilterType::Triangle);
+    let left = canvas.x(lb_frame.min_x()).round() as i64;
+    let top = (canvas.y(lb_frame.min_y()) - height as f32).round() as i64;
+    imageops::overlay(&mut canvas.image, &resized, left, top);
+
+    let (stroke, border_required) = border(&block_image.styles);
+    if border_required {
+        canvas.stroke_rect(&lb_frame, &stroke);
+    }
+}
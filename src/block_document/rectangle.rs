@@ -5,6 +5,7 @@ use crate::block_document::style::Style;
 pub struct Rectangle {
     pub frame: Option<GeoRect>,
     pub styles: Vec<Style>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl Rectangle {
@@ -12,6 +13,7 @@ impl Rectangle {
         Rectangle {
             frame,
             styles: Vec::new(),
+            source_pointer: None,
         }
     }
 
@@ -22,4 +24,8 @@ impl Rectangle {
     pub fn add_style(&mut self, style: Style) {
         self.styles.push(style);
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
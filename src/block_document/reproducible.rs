@@ -0,0 +1,100 @@
+use printpdf::lopdf::{Dictionary, Document as LoDocument, Object, ObjectId, StringFormat};
+use std::collections::HashMap;
+
+// NOTE: 同じ入力から同じバイト列の PDF になるように書き換える
+// NOTE: printpdf は辞書（画像やフォントなど）の順番とオブジェクト番号がハッシュマップの順番で変わり、ID もランダムになる
+pub fn apply(pdf: &mut LoDocument) {
+    sort_dictionary(&mut pdf.trailer);
+    for object in pdf.objects.values_mut() {
+        sort_keys(object);
+    }
+
+    renumber_objects(pdf);
+    set_document_id(pdf);
+}
+
+// NOTE: 辞書のキーを名前順にする
+fn sort_keys(object: &mut Object) {
+    match object {
+        Object::Array(array) => array.iter_mut().for_each(sort_keys),
+        Object::Dictionary(dictionary) => sort_dictionary(dictionary),
+        Object::Stream(stream) => sort_dictionary(&mut stream.dict),
+        _ => {}
+    }
+}
+
+fn sort_dictionary(dictionary: &mut Dictionary) {
+    let mut entries: Vec<(Vec<u8>, Object)> = dictionary
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    *dictionary = Dictionary::new();
+    for (key, mut value) in entries {
+        sort_keys(&mut value);
+        dictionary.set(key, value);
+    }
+}
+
+// NOTE: trailer からたどった順番でオブジェクト番号を振り直す（参照されていないオブジェクトは削除する）
+fn renumber_objects(pdf: &mut LoDocument) {
+    let order = pdf.traverse_objects(|_| {});
+    let replace: HashMap<ObjectId, ObjectId> = order
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (*id, (i as u32 + 1, 0)))
+        .collect();
+
+    let mut objects = std::mem::take(&mut pdf.objects);
+    for id in order.iter() {
+        if let Some(mut object) = objects.remove(id) {
+            replace_references(&mut object, &replace);
+            pdf.objects.insert(replace[id], object);
+        }
+    }
+
+    let mut trailer = Object::Dictionary(std::mem::take(&mut pdf.trailer));
+    replace_references(&mut trailer, &replace);
+    if let Object::Dictionary(trailer) = trailer {
+        pdf.trailer = trailer;
+    }
+
+    pdf.max_id = order.len() as u32;
+}
+
+fn replace_references(object: &mut Object, replace: &HashMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => {
+            if let Some(new_id) = replace.get(id) {
+                *id = *new_id;
+            }
+        }
+        Object::Array(array) => array.iter_mut().for_each(|item| replace_references(item, replace)),
+        Object::Dictionary(dictionary) => dictionary
+            .iter_mut()
+            .for_each(|(_, value)| replace_references(value, replace)),
+        Object::Stream(stream) => stream
+            .dict
+            .iter_mut()
+            .for_each(|(_, value)| replace_references(value, replace)),
+        _ => {}
+    }
+}
+
+// NOTE: ID を除いた内容の MD5 を ID にする（同じ入力からは同じ ID になる）
+fn set_document_id(pdf: &mut LoDocument) {
+    pdf.trailer.remove(b"ID");
+
+    let mut bytes: Vec<u8> = Vec::new();
+    pdf.save_to(&mut bytes).unwrap();
+    let id = md5::compute(&bytes).0.to_vec();
+
+    pdf.trailer.set(
+        "ID",
+        Object::Array(vec![
+            Object::String(id.clone(), StringFormat::Hexadecimal),
+            Object::String(id, StringFormat::Hexadecimal),
+        ]),
+    );
+}
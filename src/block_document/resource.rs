@@ -0,0 +1,76 @@
+use crate::block_document::data_uri;
+use ab_glyph::FontArc;
+use image::{DynamicImage, ImageError};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// NOTE: フォントと画像の読み込み結果をキャッシュする（バッチ処理で同じファイルを何度も読み込まないように）
+// NOTE: path には data URI も指定できる（キャッシュのキーは data URI そのもの）
+// NOTE: data URI はレコードごとに異なる場合があるため、キャッシュする件数を制限する
+const MAX_DATA_URI_ENTRIES: usize = 8;
+
+static FONT_DATA: OnceLock<Mutex<HashMap<String, Arc<Vec<u8>>>>> = OnceLock::new();
+static FONTS: OnceLock<Mutex<HashMap<String, FontArc>>> = OnceLock::new();
+static IMAGES: OnceLock<Mutex<HashMap<String, Arc<DynamicImage>>>> = OnceLock::new();
+
+pub fn load_font_data(font_path: &str) -> Option<Arc<Vec<u8>>> {
+    let mut font_data = FONT_DATA.get_or_init(Default::default).lock().unwrap();
+    if let Some(data) = font_data.get(font_path) {
+        return Some(data.clone());
+    }
+
+    let data = Arc::new(read(font_path).ok()?);
+    insert(&mut font_data, font_path, data.clone());
+
+    Some(data)
+}
+
+pub fn load_font(font_path: &str) -> Option<FontArc> {
+    let mut fonts = FONTS.get_or_init(Default::default).lock().unwrap();
+    if let Some(font) = fonts.get(font_path) {
+        return Some(font.clone());
+    }
+
+    let data = load_font_data(font_path)?;
+    let font = FontArc::try_from_vec(data.to_vec()).ok()?;
+    insert(&mut fonts, font_path, font.clone());
+
+    Some(font)
+}
+
+pub fn load_image(path: &str) -> Result<Arc<DynamicImage>, ImageError> {
+    let mut images = IMAGES.get_or_init(Default::default).lock().unwrap();
+    if let Some(image) = images.get(path) {
+        return Ok(image.clone());
+    }
+
+    let image = if data_uri::is_data_uri(path) {
+        Arc::new(image::load_from_memory(&read(path)?)?)
+    } else {
+        Arc::new(image::io::Reader::open(path)?.decode()?)
+    };
+    insert(&mut images, path, image.clone());
+
+    Ok(image)
+}
+
+// NOTE: data URI の件数が上限に達した場合は data URI のキャッシュを破棄する（ファイルのキャッシュは残す）
+fn insert<T>(cache: &mut HashMap<String, T>, path: &str, value: T) {
+    if data_uri::is_data_uri(path)
+        && cache.keys().filter(|key| data_uri::is_data_uri(key)).count() >= MAX_DATA_URI_ENTRIES
+    {
+        cache.retain(|key, _| !data_uri::is_data_uri(key));
+    }
+
+    cache.insert(path.to_string(), value);
+}
+
+fn read(path: &str) -> io::Result<Vec<u8>> {
+    if data_uri::is_data_uri(path) {
+        return data_uri::decode(path).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+
+    fs::read(path)
+}
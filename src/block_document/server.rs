@@ -0,0 +1,397 @@
+use crate::block_document::asset_path::AssetPaths;
+use crate::block_document::diagnostic::{Diagnostics, Severity};
+use crate::block_document::document_json;
+use crate::block_document::input_format::InputFormat;
+use crate::block_document::pdf_writer;
+use crate::block_document::template;
+use crate::block_document::validation;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::env;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::path::PathBuf;
+use std::process::{exit, Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response};
+
+pub const DEFAULT_MAX_REQUEST_SIZE: usize = 10 * 1024 * 1024;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+
+// NOTE: ワーカープロセスを起動するサブコマンド（この実行ファイルの main で run_worker を呼び出す）
+pub const WORKER_COMMAND: &str = "serve-worker";
+
+type HttpResponse = Response<Cursor<Vec<u8>>>;
+
+// NOTE: 他の言語のサービスから使用する HTTP サーバー
+// NOTE: リクエストは1件ずつ、起動したままのワーカープロセスで処理する（スキーマやフォント、画像の読み込みをリクエストごとに繰り返さないため）
+// NOTE: 制限時間を超えた場合や panic で終了した場合はワーカープロセスを破棄し、次のリクエストで起動し直す
+pub struct Server {
+    address: String,
+    asset_paths: AssetPaths,
+    format: InputFormat,
+    max_request_size: usize,
+    timeout: Duration,
+    worker: RefCell<Option<Worker>>,
+}
+
+impl Server {
+    pub fn new(address: String, asset_paths: AssetPaths) -> Server {
+        Server {
+            address,
+            asset_paths,
+            format: InputFormat::Json,
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+            worker: RefCell::new(None),
+        }
+    }
+
+    pub fn set_format(&mut self, format: InputFormat) {
+        self.format = format;
+    }
+
+    pub fn set_max_request_size(&mut self, max_request_size: usize) {
+        self.max_request_size = max_request_size;
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn run(&self) {
+        let server = tiny_http::Server::http(&self.address).unwrap_or_else(|e| {
+            eprintln!("Could not listen on {}: {}", self.address, e);
+            exit(1);
+        });
+        eprintln!("Listening on http://{}", self.address);
+
+        // NOTE: 最初のリクエストを待たせないように先に起動しておく（失敗した場合はリクエスト時に起動し直す）
+        match Worker::spawn() {
+            Ok(worker) => *self.worker.borrow_mut() = Some(worker),
+            Err(e) => eprintln!("Could not run the worker process: {}", e),
+        }
+
+        for mut request in server.incoming_requests() {
+            let started_at = Instant::now();
+            let response = self.handle(&mut request);
+            eprintln!(
+                "{} {} {} ({} ms)",
+                request.method(),
+                request.url(),
+                response.status_code().0,
+                started_at.elapsed().as_millis()
+            );
+
+            if let Err(e) = request.respond(response) {
+                eprintln!("Could not send the response: {}", e);
+            }
+        }
+    }
+
+    fn handle(&self, request: &mut Request) -> HttpResponse {
+        let path = request.url().split('?').next().unwrap_or_default().to_string();
+
+        match (request.method(), path.as_str()) {
+            (Method::Get, "/health") => json_response(200, &json!({ "status": "ok" })),
+            (Method::Post, "/render") => match self.read_document(request) {
+                Ok(json) => self.render(json),
+                Err(response) => response,
+            },
+            (Method::Post, "/validate") => match self.read_document(request) {
+                Ok(json) => self.validate(json),
+                Err(response) => response,
+            },
+            (_, "/health") | (_, "/render") | (_, "/validate") => error_response(405, "Method not allowed"),
+            _ => error_response(404, "Not found"),
+        }
+    }
+
+    // NOTE: リクエストの本文はレイアウトの JSON、またはデータを差し込む場合は {"layout": ..., "data": ...}
+    fn read_document(&self, request: &mut Request) -> Result<Value, HttpResponse> {
+        let too_large = || error_response(413, &format!("The request body exceeds {} bytes", self.max_request_size));
+        if request.body_length().is_some_and(|length| length > self.max_request_size) {
+            return Err(too_large());
+        }
+
+        let mut body = String::new();
+        request
+            .as_reader()
+            .take(self.max_request_size as u64 + 1)
+            .read_to_string(&mut body)
+            .map_err(|e| error_response(400, &format!("Could not read the request body: {}", e)))?;
+        if body.len() > self.max_request_size {
+            return Err(too_large());
+        }
+
+        let json = self
+            .format
+            .parse(&body)
+            .map_err(|e| error_response(400, &format!("Failed to parse the request body as {:?}: {}", self.format, e)))?;
+
+        match json.get("layout") {
+            Some(layout) => match json.get("data") {
+                Some(data) => Ok(template::bind(layout, data)),
+                None => Ok(layout.clone()),
+            },
+            None => Ok(json),
+        }
+    }
+
+    fn render(&self, json: Value) -> HttpResponse {
+        match self.run_worker(WorkerAction::Render, json) {
+            Ok((WorkerStatus::Success, bytes)) => Response::from_data(bytes)
+                .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/pdf"[..]).unwrap()),
+            Ok((WorkerStatus::Diagnostics, bytes)) => json_bytes_response(422, bytes),
+            Err(response) => response,
+        }
+    }
+
+    fn validate(&self, json: Value) -> HttpResponse {
+        match self.run_worker(WorkerAction::Validate, json) {
+            Ok((WorkerStatus::Success, bytes)) => json_bytes_response(200, bytes),
+            Ok((WorkerStatus::Diagnostics, _)) => error_response(500, "Validation failed"),
+            Err(response) => response,
+        }
+    }
+
+    // NOTE: 制限時間を超えた場合や応答の前にワーカープロセスが終了した場合は、ワーカープロセスを破棄してエラーを返す
+    fn run_worker(&self, action: WorkerAction, json: Value) -> Result<(WorkerStatus, Vec<u8>), HttpResponse> {
+        let failed = |e: io::Error| {
+            eprintln!("Could not run the worker process: {}", e);
+            error_response(500, "Rendering failed")
+        };
+
+        let mut slot = self.worker.borrow_mut();
+        // NOTE: リクエストを待つ間に終了していた場合も起動し直す
+        if slot.as_mut().is_some_and(|worker| !matches!(worker.child.try_wait(), Ok(None))) {
+            slot.take();
+        }
+        let worker = match slot.as_mut() {
+            Some(worker) => worker,
+            None => slot.insert(Worker::spawn().map_err(failed)?),
+        };
+
+        let request = WorkerRequest {
+            action,
+            json,
+            base_dir: self.asset_paths.base_dir.clone(),
+            asset_roots: self.asset_paths.asset_roots.clone(),
+        };
+        let result = match worker.send(&request) {
+            Ok(()) => worker.responses.recv_timeout(self.timeout),
+            Err(_) => Err(RecvTimeoutError::Disconnected),
+        };
+
+        match result {
+            Ok(Ok(response)) => Ok(response),
+            Err(RecvTimeoutError::Timeout) => {
+                slot.take().unwrap().kill();
+                Err(error_response(
+                    503,
+                    &format!("Rendering timed out after {} seconds", self.timeout.as_secs()),
+                ))
+            }
+            // NOTE: 書き込みや読み込みのエラーはワーカープロセスの終了（panic など）によるもの
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => {
+                let mut worker = slot.take().unwrap();
+                let status = worker.child.wait();
+                eprintln!("The worker process exited unexpectedly: {:?}", status);
+                Err(error_response(500, "Rendering failed"))
+            }
+        }
+    }
+}
+
+// NOTE: serve-worker の子プロセス。レスポンスは読み込み用のスレッドから受け取る（制限時間付きで待つため）
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    responses: Receiver<io::Result<(WorkerStatus, Vec<u8>)>>,
+}
+
+impl Worker {
+    fn spawn() -> io::Result<Worker> {
+        let mut child = Command::new(env::current_exe()?)
+            .arg(WORKER_COMMAND)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().unwrap();
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let response = read_response(&mut stdout);
+                let failed = response.is_err();
+                if sender.send(response).is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Ok(Worker { child, stdin, responses })
+    }
+
+    fn send(&mut self, request: &WorkerRequest) -> io::Result<()> {
+        write_message(&mut self.stdin, &serde_json::to_vec(request).unwrap())
+    }
+
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn read_response(stdout: &mut BufReader<ChildStdout>) -> io::Result<(WorkerStatus, Vec<u8>)> {
+    let header = read_message(stdout)?;
+    let status = serde_json::from_slice(&header).map_err(io::Error::other)?;
+    Ok((status, read_message(stdout)?))
+}
+
+// NOTE: ワーカープロセスとのメッセージは「バイト数の行」+ 本文（PDF をそのまま送るため）
+fn write_message(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    writeln!(writer, "{}", bytes.len())?;
+    writer.write_all(bytes)?;
+    writer.flush()
+}
+
+// NOTE: 終端（相手のプロセスの終了）の場合は UnexpectedEof
+fn read_message(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let length: usize = line.trim_end().parse().map_err(io::Error::other)?;
+
+    let mut bytes = vec![0; length];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WorkerStatus {
+    Success,
+    Diagnostics,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WorkerAction {
+    Render,
+    Validate,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorkerRequest {
+    action: WorkerAction,
+    json: Value,
+    base_dir: PathBuf,
+    asset_roots: Vec<PathBuf>,
+}
+
+// NOTE: serve のワーカープロセスとして、標準入力が閉じられるまでリクエストを処理する
+// NOTE: レスポンスはステータス（JSON）と本文の2つのメッセージ
+pub fn run_worker() {
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+
+    loop {
+        let request = match read_message(&mut stdin) {
+            Ok(bytes) => serde_json::from_slice::<WorkerRequest>(&bytes).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => exit(0),
+            Err(e) => Err(e),
+        };
+        let request = request.unwrap_or_else(|e| {
+            eprintln!("Invalid worker request: {}", e);
+            exit(1);
+        });
+        let asset_paths = AssetPaths {
+            base_dir: request.base_dir,
+            asset_roots: request.asset_roots,
+        };
+
+        let (status, bytes) = match request.action {
+            WorkerAction::Render => match render(&request.json, &asset_paths) {
+                Ok(bytes) => (WorkerStatus::Success, bytes),
+                Err(diagnostics) => (
+                    WorkerStatus::Diagnostics,
+                    serde_json::to_vec(&json!({ "diagnostics": diagnostics })).unwrap(),
+                ),
+            },
+            WorkerAction::Validate => (
+                WorkerStatus::Success,
+                serde_json::to_vec(&validate(&request.json, &asset_paths)).unwrap(),
+            ),
+        };
+
+        if let Err(e) = write_message(&mut stdout, &serde_json::to_vec(&status).unwrap())
+            .and_then(|_| write_message(&mut stdout, &bytes))
+        {
+            eprintln!("Could not write the worker response: {}", e);
+            exit(1);
+        }
+    }
+}
+
+// NOTE: エラーがある場合は diagnostics を返す
+fn render(json: &Value, asset_paths: &AssetPaths) -> Result<Vec<u8>, Value> {
+    let diagnostics = Diagnostics::new(None);
+    let document = document_json::parse_with_diagnostics(json, asset_paths, &diagnostics);
+    let Some(document) = document.filter(|_| !diagnostics.has_errors()) else {
+        return Err(diagnostics_json(&diagnostics));
+    };
+
+    let mut bytes: Vec<u8> = Vec::new();
+    // NOTE: メモリへの書き込みは失敗しない
+    pdf_writer::save_laid_out(vec![pdf_writer::layout(&document, false)], &mut bytes, false, false).unwrap();
+    Ok(bytes)
+}
+
+fn validate(json: &Value, asset_paths: &AssetPaths) -> Value {
+    let diagnostics = Diagnostics::new(None);
+    if let Some(document) = document_json::parse_with_diagnostics(json, asset_paths, &diagnostics)
+        && !diagnostics.has_errors()
+    {
+        validation::check_layout(&document, &diagnostics);
+    }
+
+    json!({
+        "valid": !diagnostics.has_errors(),
+        "errors": diagnostics.count(Severity::Error),
+        "warnings": diagnostics.count(Severity::Warning),
+        "diagnostics": diagnostics_json(&diagnostics),
+    })
+}
+
+fn diagnostics_json(diagnostics: &Diagnostics) -> Value {
+    diagnostics
+        .entries()
+        .into_iter()
+        .map(|(severity, pointer, message)| {
+            let severity = match severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            json!({ "severity": severity, "pointer": pointer, "message": message })
+        })
+        .collect()
+}
+
+fn json_response(status_code: u16, json: &Value) -> HttpResponse {
+    json_bytes_response(status_code, serde_json::to_vec(json).unwrap())
+}
+
+fn json_bytes_response(status_code: u16, bytes: Vec<u8>) -> HttpResponse {
+    Response::from_data(bytes)
+        .with_status_code(status_code)
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap())
+}
+
+fn error_response(status_code: u16, message: &str) -> HttpResponse {
+    json_response(status_code, &json!({ "error": message }))
+}
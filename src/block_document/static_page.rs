@@ -1,9 +1,12 @@
 use crate::block_document::block::Block;
+use crate::block_document::data_uri;
 use crate::block_document::direction::Direction;
 use crate::block_document::document::px_to_mm;
 use crate::block_document::geometry::{GeoPoint, GeoRect, GeoSize};
 use crate::block_document::image::Image;
-use crate::block_document::style::{Style, TextWrapMode};
+use crate::block_document::page_number::PageNumbering;
+use crate::block_document::resource::load_image;
+use crate::block_document::style::{Space, Style, TextWrapMode};
 use crate::block_document::text::Text;
 use crate::block_document::text_renderer::{measure_text, wrap_text_by_character};
 use image::{GenericImageView, ImageError};
@@ -12,21 +15,59 @@ use image::{GenericImageView, ImageError};
 pub struct StaticPage {
     pub blocks: Vec<Block>,
     pub auto_pagination: bool,
+    pub page_numbering: PageNumbering,
+    pub page_size: Option<GeoSize>,
+    pub margin: Option<Space>,
+}
+
+impl Default for StaticPage {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StaticPage {
     pub fn new() -> StaticPage {
-        StaticPage { blocks: Vec::new(), auto_pagination: false }
+        StaticPage {
+            blocks: Vec::new(),
+            auto_pagination: false,
+            page_numbering: PageNumbering::default(),
+            page_size: None,
+            margin: None,
+        }
     }
 
     pub fn set_auto_pagination(&mut self, auto_pagination: bool) {
         self.auto_pagination = auto_pagination;
     }
+
+    pub fn set_page_numbering(&mut self, page_numbering: PageNumbering) {
+        self.page_numbering = page_numbering;
+    }
+
+    pub fn set_page_size(&mut self, page_size: GeoSize) {
+        self.page_size = Some(page_size);
+    }
+
+    pub fn set_margin(&mut self, margin: Space) {
+        self.margin = Some(margin);
+    }
     
     pub fn add_block(&mut self, block: Block) {
         self.blocks.push(block);
     }
 
+    // NOTE: ページ設定を引き継いだページを作る
+    fn with_blocks(&self, blocks: Vec<Block>) -> StaticPage {
+        StaticPage {
+            blocks,
+            auto_pagination: self.auto_pagination,
+            page_numbering: self.page_numbering.clone(),
+            page_size: self.page_size.clone(),
+            margin: self.margin.clone(),
+        }
+    }
+
     // NOTE: 座標を計算する
     pub fn apply_constraints(
         &mut self,
@@ -56,7 +97,7 @@ impl StaticPage {
                 }
 
                 if parent_frame.max_y() < drawn_frame.max_y() + frame.as_ref().unwrap_or(&GeoRect::default()).height() {
-                    containers.push(StaticPage { blocks, auto_pagination });
+                    containers.push(self.with_blocks(blocks));
 
                     drawn_frame = GeoRect::new(0.0, 0.0, parent_frame.min_x(), parent_frame.min_y());
 
@@ -83,7 +124,7 @@ impl StaticPage {
             }
 
             if blocks.len() > 0 {
-                containers.push(StaticPage { blocks, auto_pagination });
+                containers.push(self.with_blocks(blocks));
             }
 
             return containers;
@@ -108,7 +149,7 @@ impl StaticPage {
         }
 
         let mut containers: Vec<StaticPage> = Vec::new();
-        containers.push(StaticPage { blocks, auto_pagination });
+        containers.push(self.with_blocks(blocks));
 
         containers
     }
@@ -586,11 +627,7 @@ impl StaticPage {
     }
 
     fn get_image_dimensions(path: &str) -> Result<(u32, u32), ImageError> {
-        image::io::Reader::open(path)
-            .map_err(|e| ImageError::from(e))?
-            .decode()
-            .map(|image| image.dimensions())
-            .map_err(ImageError::from)
+        load_image(path).map(|image| image.dimensions())
     }
 
     fn calculate_image_constraints(
@@ -634,7 +671,8 @@ impl StaticPage {
                 Err(e) => {
                     panic!(
                         "Error reading image dimensions for {}: {}",
-                        block_image.path, e
+                        data_uri::display(&block_image.path),
+                        e
                     );
                 }
             }
@@ -1,4 +1,9 @@
-#[derive(Debug, Clone)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "crate::block_document::document_serde::StyleJson")]
+#[serde(into = "crate::block_document::document_serde::StyleJson")]
 pub enum Style {
     TextFillColor(RgbColor),
     TextOutlineColor(RgbColor),
@@ -13,10 +18,14 @@ pub enum Style {
     TextWrap(TextWrap),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "rgb")]
 pub struct RgbColor {
+    #[serde(rename = "red")]
     pub r: u8, // NOTE: 0-255
+    #[serde(rename = "green")]
     pub g: u8, // NOTE: 0-255
+    #[serde(rename = "blue")]
     pub b: u8, // NOTE: 0-255
 }
 
@@ -39,39 +48,54 @@ pub enum BorderStyle {
     Dash(i64),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "space")]
 pub struct Space {
-    pub top: f32,    // NOTE: mm
-    pub right: f32,  // NOTE: mm
+    #[schemars(range(min = 0.0))]
+    pub top: f32, // NOTE: mm
+    #[schemars(range(min = 0.0))]
+    pub right: f32, // NOTE: mm
+    #[schemars(range(min = 0.0))]
     pub bottom: f32, // NOTE: mm
-    pub left: f32,   // NOTE: mm
+    #[schemars(range(min = 0.0))]
+    pub left: f32, // NOTE: mm
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(inline)]
 pub enum HorizontalAlignment {
     Left,
     Center,
     Right,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(inline)]
 pub enum VerticalAlignment {
     Top,
     Center,
     Bottom,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "alignment")]
 pub struct Alignment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub horizontal: Option<HorizontalAlignment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub vertical: Option<VerticalAlignment>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "text_wrap")]
 pub struct TextWrap {
     pub mode: TextWrapMode,
     #[allow(dead_code)]
+    #[serde(default)]
     pub break_anywhere: bool, // NOTE: 緊急時の文字折り返し
+    #[serde(default)]
     pub overflow: TextOverflow,
 }
 
@@ -85,15 +109,20 @@ impl Default for TextWrap {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(inline)]
 pub enum TextWrapMode {
     None,
     Word,
     Character,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(inline)]
 pub enum TextOverflow {
+    #[default]
     Clip,
     Ellipsis,
 }
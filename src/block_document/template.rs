@@ -0,0 +1,324 @@
+use serde_json::{Map, Value};
+
+const KEY_REPEAT: &str = "repeat";
+const KEY_REPEAT_AS: &str = "as";
+const KEY_IF: &str = "if";
+const KEY_UNLESS: &str = "unless";
+const DEFAULT_REPEAT_AS: &str = "item";
+const LOOP_VARIABLE: &str = "loop";
+const KEY_COMPONENTS: &str = "components"; // NOTE: コンポーネントは配置時の引数で差し込む
+
+// NOTE: テンプレートにデータを差し込む（document_json::parse の前に実行する）
+// NOTE: - 文字列中の {{path.to.value}} をデータの値で置き換える
+// NOTE: - 配列内のオブジェクトの "repeat" で配列の要素ごとにオブジェクトを出力する
+// NOTE: - オブジェクトの "if" / "unless" で出力するかどうかを決める（配列の外のオブジェクトはキーを省略する）
+pub fn bind(template: &Value, data: &Value) -> Value {
+    let scope = Scope {
+        data,
+        variables: Vec::new(),
+    };
+
+    match template {
+        Value::Object(map) if !is_visible(map, &scope) => Value::Null,
+        _ => bind_value(template, &scope),
+    }
+}
+
+struct Scope<'a> {
+    data: &'a Value,
+    variables: Vec<(String, Value)>, // NOTE: repeat で追加される変数（後に追加されたものが優先）
+}
+
+impl<'a> Scope<'a> {
+    fn with_variables(&self, variables: Vec<(String, Value)>) -> Scope<'a> {
+        let mut scoped_variables = self.variables.clone();
+        scoped_variables.extend(variables);
+
+        Scope {
+            data: self.data,
+            variables: scoped_variables,
+        }
+    }
+
+    fn lookup(&self, path: &str) -> Option<&Value> {
+        let mut segments = path.split('.').map(|segment| segment.trim());
+        let first = segments.next()?;
+
+        let root = self
+            .variables
+            .iter()
+            .rev()
+            .find(|(name, _)| name == first)
+            .map(|(_, value)| value);
+
+        match root {
+            Some(value) => segments.try_fold(value, lookup_segment),
+            None => path
+                .split('.')
+                .map(|segment| segment.trim())
+                .try_fold(self.data, lookup_segment),
+        }
+    }
+}
+
+fn lookup_segment<'v>(value: &'v Value, segment: &str) -> Option<&'v Value> {
+    match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(values) => segment.parse::<usize>().ok().and_then(|index| values.get(index)),
+        _ => None,
+    }
+}
+
+fn bind_value(value: &Value, scope: &Scope) -> Value {
+    match value {
+        Value::String(string) => bind_string(string, scope),
+        Value::Array(values) => Value::Array(bind_array(values, scope)),
+        Value::Object(map) => Value::Object(bind_object(map, scope)),
+        _ => value.clone(),
+    }
+}
+
+fn bind_array(values: &[Value], scope: &Scope) -> Vec<Value> {
+    let mut bound_values: Vec<Value> = Vec::new();
+
+    for value in values.iter() {
+        let Some(map) = value.as_object() else {
+            bound_values.push(bind_value(value, scope));
+
+            continue;
+        };
+
+        if let Some(repeat) = map.get(KEY_REPEAT).and_then(|repeat| repeat.as_str()) {
+            let name = map
+                .get(KEY_REPEAT_AS)
+                .and_then(|name| name.as_str())
+                .unwrap_or(DEFAULT_REPEAT_AS);
+
+            let items = match scope.lookup(strip_braces(repeat)) {
+                Some(Value::Array(items)) => items.clone(),
+                Some(Value::Null) | None => Vec::new(),
+                Some(item) => vec![item.clone()],
+            };
+
+            let count = items.len();
+            for (index, item) in items.into_iter().enumerate() {
+                let item_scope = scope.with_variables(vec![
+                    (name.to_string(), item),
+                    (LOOP_VARIABLE.to_string(), loop_variable(index, count)),
+                ]);
+
+                if is_visible(map, &item_scope) {
+                    bound_values.push(Value::Object(bind_object(map, &item_scope)));
+                }
+            }
+
+            continue;
+        }
+
+        if is_visible(map, scope) {
+            bound_values.push(Value::Object(bind_object(map, scope)));
+        }
+    }
+
+    bound_values
+}
+
+fn bind_object(map: &Map<String, Value>, scope: &Scope) -> Map<String, Value> {
+    map.iter()
+        .filter(|(key, _)| ![KEY_REPEAT, KEY_REPEAT_AS, KEY_IF, KEY_UNLESS].contains(&key.as_str()))
+        .filter_map(|(key, value)| match (key.as_str(), value) {
+            (KEY_COMPONENTS, _) => Some((key.clone(), value.clone())),
+            // NOTE: 配列の外のオブジェクト（dynamic ページの content など）は条件を満たさない場合にキーを省略する
+            (_, Value::Object(child)) if !is_visible(child, scope) => None,
+            _ => Some((key.clone(), bind_value(value, scope))),
+        })
+        .collect()
+}
+
+// NOTE: 文字列全体が {{path}} の場合は値の型を保持する（数値や真偽値を差し込めるように）
+fn bind_string(string: &str, scope: &Scope) -> Value {
+    let trimmed = string.trim();
+    let path = strip_braces(trimmed);
+    if trimmed.starts_with("{{") && trimmed.ends_with("}}") && !path.contains(['{', '}']) {
+        return match scope.lookup(path) {
+            Some(Value::String(value)) => Value::String(value.clone()),
+            Some(Value::Null) | None => Value::String(String::new()),
+            Some(value) => value.clone(),
+        };
+    }
+
+    let mut bound = String::new();
+    let mut rest = string;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        // NOTE: {{a {{b}} }} のように入れ子の場合は内側の {{b}} を置き換える
+        let start = rest[..end].rfind("{{").unwrap_or(start);
+
+        bound.push_str(&rest[..start]);
+        let path = &rest[start + 2..end];
+        bound.push_str(&to_text(scope.lookup(path.trim())));
+        rest = &rest[end + 2..];
+    }
+    bound.push_str(rest);
+
+    Value::String(bound)
+}
+
+fn is_visible(map: &Map<String, Value>, scope: &Scope) -> bool {
+    let is_if_satisfied = map
+        .get(KEY_IF)
+        .map(|condition| is_truthy(&resolve_condition(condition, scope)))
+        .unwrap_or(true);
+    let is_unless_satisfied = map
+        .get(KEY_UNLESS)
+        .map(|condition| !is_truthy(&resolve_condition(condition, scope)))
+        .unwrap_or(true);
+
+    is_if_satisfied && is_unless_satisfied
+}
+
+// NOTE: 条件には "path.to.value" または "{{path.to.value}}" を指定できる
+fn resolve_condition(condition: &Value, scope: &Scope) -> Value {
+    match condition {
+        Value::String(path) => scope.lookup(strip_braces(path)).cloned().unwrap_or(Value::Null),
+        _ => condition.clone(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().map(|number| number != 0.0).unwrap_or(false),
+        Value::String(string) => !string.is_empty(),
+        Value::Array(values) => !values.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn to_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(string)) => string.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn strip_braces(path: &str) -> &str {
+    let path = path.trim();
+
+    path.strip_prefix("{{")
+        .and_then(|path| path.strip_suffix("}}"))
+        .unwrap_or(path)
+        .trim()
+}
+
+// NOTE: {{loop.index}} は1始まり、{{loop.index0}} は0始まり
+fn loop_variable(index: usize, count: usize) -> Value {
+    serde_json::json!({
+        "index": index + 1,
+        "index0": index,
+        "first": index == 0,
+        "last": index + 1 == count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn bind_embedded_values() {
+        let data = json!({ "customer": { "name": "山田", "age": 30 }, "items": ["a", "b"] });
+
+        assert_eq!(
+            bind(&json!("{{ customer.name }} 様 ({{customer.age}})"), &data),
+            json!("山田 様 (30)")
+        );
+        assert_eq!(bind(&json!("{{items.1}}"), &data), json!("b"));
+    }
+
+    #[test]
+    fn bind_keeps_value_type() {
+        let data = json!({ "size": 12.5, "flag": true, "frame": { "x": 1 } });
+
+        assert_eq!(bind(&json!("{{size}}"), &data), json!(12.5));
+        assert_eq!(bind(&json!(" {{flag}} "), &data), json!(true));
+        assert_eq!(bind(&json!("{{frame}}"), &data), json!({ "x": 1 }));
+    }
+
+    #[test]
+    fn bind_missing_keys() {
+        let data = json!({ "a": null });
+
+        assert_eq!(bind(&json!("{{missing}}"), &data), json!(""));
+        assert_eq!(bind(&json!("{{a}}"), &data), json!(""));
+        assert_eq!(bind(&json!("[{{missing.deep}}]"), &data), json!("[]"));
+        assert_eq!(bind(&json!("[{{}}]"), &data), json!("[]"));
+    }
+
+    #[test]
+    fn bind_unclosed_and_nested_braces() {
+        let data = json!({ "a": "A", "b": "B" });
+
+        assert_eq!(bind(&json!("{{a}} {{b"), &data), json!("A {{b"));
+        assert_eq!(bind(&json!("}} {{a}}"), &data), json!("}} A"));
+        assert_eq!(bind(&json!("{{x {{a}} }}"), &data), json!("{{x A }}"));
+        assert_eq!(bind(&json!("{{{a}}}"), &data), json!("{A}"));
+    }
+
+    #[test]
+    fn bind_repeat_and_conditions() {
+        let template = json!([
+            { "repeat": "items", "as": "row", "text": "{{loop.index}}:{{row.name}}", "if": "row.visible" },
+            { "text": "empty", "unless": "items" },
+            { "text": "not an array", "repeat": "single" },
+        ]);
+        let data = json!({
+            "items": [{ "name": "a", "visible": true }, { "name": "b" }, { "name": "c", "visible": 1 }],
+            "single": { "name": "s" },
+        });
+
+        assert_eq!(
+            bind(&template, &data),
+            json!([{ "text": "1:a" }, { "text": "3:c" }, { "text": "not an array" }])
+        );
+        assert_eq!(bind(&template, &json!({})), json!([{ "text": "empty" }]));
+    }
+
+    #[test]
+    fn bind_conditions_outside_arrays() {
+        let template = json!({
+            "first": { "common_objects": [], "if": "show_first" },
+            "last": { "common_objects": [], "unless": "hide_last" },
+            "content": { "objects": [{ "text": "{{a}}" }] },
+        });
+
+        assert_eq!(
+            bind(&template, &json!({ "a": "A", "hide_last": true })),
+            json!({ "content": { "objects": [{ "text": "A" }] } })
+        );
+        assert_eq!(
+            bind(&template, &json!({ "a": "A", "show_first": true })),
+            json!({
+                "first": { "common_objects": [] },
+                "last": { "common_objects": [] },
+                "content": { "objects": [{ "text": "A" }] },
+            })
+        );
+        assert_eq!(bind(&json!({ "text": "x", "if": "missing" }), &json!({})), Value::Null);
+    }
+
+    #[test]
+    fn bind_keeps_component_definitions() {
+        let template = json!({ "components": { "label": { "text": "{{t}}" } }, "text": "{{t}}" });
+
+        assert_eq!(
+            bind(&template, &json!({ "t": "x" })),
+            json!({ "components": { "label": { "text": "{{t}}" } }, "text": "x" })
+        );
+    }
+}
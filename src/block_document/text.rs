@@ -1,6 +1,7 @@
 use crate::block_document::geometry::{GeoRect, GeoSize};
 use crate::block_document::style::{Style, TextWrap};
-use crate::block_document::text_renderer::WrappedText;
+use crate::block_document::text_renderer::{measure_text, wrap_text_by_character, WrappedText};
+use crate::block_document::text_variable::{TextVariables, PAGE_PLACEHOLDERS};
 
 #[derive(Debug, Clone)]
 pub struct Text {
@@ -12,6 +13,8 @@ pub struct Text {
     pub wrap_width: Option<f32>, // NOTE: mm
     pub wrapped_size: Option<GeoSize>,
     pub wrapped_text: Option<WrappedText>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
+    pub variable_text: Option<String>, // NOTE: ページ変数の幅を確保する前のテキスト（reserve_variables）
 }
 
 impl Text {
@@ -30,6 +33,8 @@ impl Text {
             wrap_width: None,
             wrapped_size: None,
             wrapped_text: None,
+            source_pointer: None,
+            variable_text: None,
         }
     }
 
@@ -103,4 +108,102 @@ impl Text {
             self.text.lines().map(|s| s.to_string()).collect()
         }
     }
+
+    pub fn has_page_variables(&self) -> bool {
+        PAGE_PLACEHOLDERS.iter().any(|placeholder| self.text.contains(placeholder))
+    }
+
+    // NOTE: レイアウト前にページ変数を全ページの値のうち最も幅の広い値で置き換え、置き換え後の幅を確保する
+    // NOTE: （レイアウト後に実際の値で置き換えても、並べて配置した要素と重ならないように）
+    pub fn reserve_variables(&mut self, page_variables: &[TextVariables], font_path: &str) {
+        if !self.has_page_variables() {
+            return;
+        }
+
+        let use_font_path = self.font_path.clone().unwrap_or(font_path.to_string());
+        let mut text = self.text.clone();
+        for placeholder in PAGE_PLACEHOLDERS.iter() {
+            if !text.contains(placeholder) {
+                continue;
+            }
+
+            let widest = page_variables
+                .iter()
+                .filter_map(|variables| variables.value(placeholder))
+                .map(|value| {
+                    let width = measure_text(value, self.font_size, &use_font_path).width;
+                    (value, width)
+                })
+                .fold(None, |widest: Option<(&str, f32)>, (value, width)| match widest {
+                    Some((_, widest_width)) if widest_width >= width => widest,
+                    _ => Some((value, width)),
+                });
+            text = text.replace(placeholder, widest.map(|(value, _)| value).unwrap_or_default());
+        }
+
+        self.variable_text = Some(self.text.clone());
+        self.text = text;
+    }
+
+    // NOTE: 変数を置き換える（レイアウト済みの場合はサイズを再計算する）
+    pub fn replace_variables(&mut self, variables: &TextVariables, font_path: &str) {
+        if let Some(variable_text) = self.variable_text.take() {
+            self.text = variable_text;
+        }
+        if !self.text.contains("${") {
+            return;
+        }
+
+        let text = variables.replace(&self.text);
+        if text == self.text {
+            return;
+        }
+
+        self.text = text;
+
+        let previous_size = match &self.wrapped_size {
+            Some(size) => size.clone(),
+            None => return,
+        };
+
+        // NOTE: frame のサイズがテキストサイズから決まっている場合は追従させる
+        let is_auto_sized = self.frame.as_ref().and_then(|frame| frame.size.as_ref()).is_some_and(
+            |size| size.width == previous_size.width && size.height == previous_size.height,
+        );
+
+        let use_font_path = self.font_path.clone().unwrap_or(font_path.to_string());
+        let size = match (&self.wrapped_text, self.get_available_width()) {
+            (Some(_), Some(available_width)) => {
+                let available_height = if is_auto_sized {
+                    None
+                } else {
+                    self.get_available_height()
+                };
+
+                let wrapped = wrap_text_by_character(
+                    &self.text,
+                    self.font_size,
+                    &use_font_path,
+                    available_width,
+                    available_height,
+                    &self.get_text_wrap(),
+                );
+                let size = wrapped.total_size.clone();
+                self.set_wrapped_text(wrapped);
+
+                size
+            }
+            _ => measure_text(&self.text, self.font_size, &use_font_path),
+        };
+
+        if is_auto_sized && let Some(frame) = self.frame.as_mut() {
+            frame.size = Some(size.clone());
+        }
+
+        self.set_text_size(size);
+    }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
@@ -1,8 +1,8 @@
+use crate::block_document::data_uri;
 use crate::block_document::geometry::GeoSize;
 use crate::block_document::style::{TextOverflow, TextWrap};
-use ab_glyph::{Font, FontVec, ScaleFont};
-use std::fs::File;
-use std::io::Read;
+use crate::block_document::resource::load_font;
+use ab_glyph::{Font, ScaleFont};
 use std::process::exit;
 
 // 折り返し結果を表現するデータ構造
@@ -16,22 +16,14 @@ pub struct WrappedLine {
 pub struct WrappedText {
     pub lines: Vec<WrappedLine>,
     pub total_size: GeoSize,
-    #[allow(dead_code)] // 将来の機能拡張用
     pub is_truncated: bool, // オーバーフロー時に切り取られたかどうか
 }
 
-pub fn measure_text(text: &String, font_size: f32, font_path: &String) -> GeoSize {
-    let file = File::open(font_path).map_err(|_| {
-        eprintln!("Failed to open font file: {}.", font_path);
-    });
-    let font = match file {
-        Ok(mut file) => {
-            let mut font_data = Vec::new();
-            file.read_to_end(&mut font_data).expect("Cannot read font");
-            FontVec::try_from_vec(font_data).unwrap()
-        }
-        Err(_) => {
-            eprintln!("Failed to open font file: {}.", font_path);
+pub fn measure_text(text: &str, font_size: f32, font_path: &str) -> GeoSize {
+    let font = match load_font(font_path) {
+        Some(font) => font,
+        None => {
+            eprintln!("Failed to open font file: {}.", data_uri::display(font_path));
             exit(1);
         }
     };
@@ -82,23 +74,16 @@ pub fn measure_text(text: &String, font_size: f32, font_path: &String) -> GeoSiz
 pub fn wrap_text_by_character(
     text: &String,
     font_size: f32,
-    font_path: &String,
+    font_path: &str,
     available_width: f32,
     available_height: Option<f32>,
     text_wrap: &TextWrap,
 ) -> WrappedText {
     // NOTE: フォントの読み込み
-    let file = File::open(font_path).map_err(|_| {
-        eprintln!("Failed to open font file: {}.", font_path);
-    });
-    let font = match file {
-        Ok(mut file) => {
-            let mut font_data = Vec::new();
-            file.read_to_end(&mut font_data).expect("Cannot read font");
-            FontVec::try_from_vec(font_data).unwrap()
-        }
-        Err(_) => {
-            eprintln!("Failed to open font file: {}.", font_path);
+    let font = match load_font(font_path) {
+        Some(font) => font,
+        None => {
+            eprintln!("Failed to open font file: {}.", data_uri::display(font_path));
             exit(1);
         }
     };
@@ -219,11 +204,11 @@ pub fn wrap_text_by_character(
 fn truncate_with_ellipsis(
     text: &String,
     font_size: f32,
-    font_path: &String,
+    font_path: &str,
     available_width: f32,
 ) -> String {
     let ellipsis = "...";
-    let ellipsis_size = measure_text(&ellipsis.to_string(), font_size, font_path);
+    let ellipsis_size = measure_text(ellipsis, font_size, font_path);
 
     // NOTE: 省略記号だけで枠を超える場合は省略記号のみ返す
     if ellipsis_size.width >= available_width {
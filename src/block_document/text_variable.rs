@@ -0,0 +1,101 @@
+use crate::block_document::page_number::PageCounter;
+use time::OffsetDateTime;
+
+pub const CURRENT_PAGE_NUMBER_PLACEHOLDER: &str = "${CURRENT_PAGE_NUMBER}";
+pub const TOTAL_PAGES_PLACEHOLDER: &str = "${TOTAL_PAGES}";
+pub const SECTION_PAGE_PLACEHOLDER: &str = "${SECTION_PAGE}";
+pub const SECTION_PAGES_PLACEHOLDER: &str = "${SECTION_PAGES}";
+pub const DOCUMENT_TITLE_PLACEHOLDER: &str = "${DOCUMENT_TITLE}";
+pub const DATE_PLACEHOLDER: &str = "${DATE}";
+
+// NOTE: ページ分割後に値が決まる変数
+pub const PAGE_PLACEHOLDERS: [&str; 4] = [
+    CURRENT_PAGE_NUMBER_PLACEHOLDER,
+    TOTAL_PAGES_PLACEHOLDER,
+    SECTION_PAGE_PLACEHOLDER,
+    SECTION_PAGES_PLACEHOLDER,
+];
+
+// NOTE: テキスト中の ${...} を置き換える値
+#[derive(Debug, Clone, Default)]
+pub struct TextVariables {
+    values: Vec<(&'static str, String)>,
+}
+
+impl TextVariables {
+    // NOTE: ページに依存しない変数（レイアウト前に置き換える）
+    pub fn new(title: &str, date: &OffsetDateTime) -> TextVariables {
+        TextVariables {
+            values: vec![
+                (DOCUMENT_TITLE_PLACEHOLDER, title.to_string()),
+                (
+                    DATE_PLACEHOLDER,
+                    format!(
+                        "{:04}-{:02}-{:02}",
+                        date.year(),
+                        date.month() as u8,
+                        date.day()
+                    ),
+                ),
+            ],
+        }
+    }
+
+    // NOTE: ページに依存する変数（ページ分割後に置き換える）
+    pub fn with_page(&self, page_counter: &PageCounter, total_pages: u32) -> TextVariables {
+        let number_style = &page_counter.number_style;
+        let format = |number: u32| match page_counter.number {
+            Some(_) => number_style.format(number),
+            None => String::new(),
+        };
+
+        let mut values = self.values.clone();
+        values.push((
+            CURRENT_PAGE_NUMBER_PLACEHOLDER,
+            format(page_counter.number.unwrap_or(0)),
+        ));
+        values.push((TOTAL_PAGES_PLACEHOLDER, format(total_pages)));
+        values.push((SECTION_PAGE_PLACEHOLDER, format(page_counter.section_page)));
+        values.push((SECTION_PAGES_PLACEHOLDER, format(page_counter.section_pages)));
+
+        TextVariables { values }
+    }
+
+    pub fn value(&self, placeholder: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(name, _)| *name == placeholder)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn replace(&self, text: &str) -> String {
+        let mut replaced = text.to_string();
+        for (placeholder, value) in self.values.iter() {
+            replaced = replaced.replace(placeholder, value);
+        }
+
+        replaced
+    }
+}
+
+// NOTE: 再現可能なビルドの慣例に従い、SOURCE_DATE_EPOCH が指定されている場合はその日時（UTC）を使用する
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+// NOTE: is_reproducible の場合は PDF の作成日時と同じ日時（reproducible_date）を使用する
+pub fn now(is_reproducible: bool) -> OffsetDateTime {
+    if is_reproducible {
+        return reproducible_date();
+    }
+
+    source_date().unwrap_or_else(|| OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc()))
+}
+
+// NOTE: 再現可能な PDF の作成日時・更新日時（SOURCE_DATE_EPOCH が未指定の場合は 1970-01-01）
+pub fn reproducible_date() -> OffsetDateTime {
+    source_date().unwrap_or(OffsetDateTime::UNIX_EPOCH)
+}
+
+fn source_date() -> Option<OffsetDateTime> {
+    let timestamp = std::env::var(SOURCE_DATE_EPOCH).ok()?.trim().parse::<i64>().ok()?;
+    OffsetDateTime::from_unix_timestamp(timestamp).ok()
+}
@@ -0,0 +1,87 @@
+use crate::block_document::block::Block;
+use crate::block_document::diagnostic::Diagnostics;
+use crate::block_document::document::Document;
+use crate::block_document::geometry::{GeoPoint, GeoSize};
+use crate::block_document::pdf_writer;
+use crate::block_document::style::TextOverflow;
+use std::collections::HashSet;
+
+// NOTE: ページの外かどうかの判定の許容誤差（mm）
+const TOLERANCE: f32 = 0.01;
+
+const WARNING_OUTSIDE_PAGE: &str = "outside_page";
+const WARNING_CLIPPED_TEXT: &str = "clipped_text";
+
+// NOTE: PDF を出力せずにレイアウトを計算して、意図しない配置になりそうなブロックを警告する
+// NOTE: 共通オブジェクトなど複数のページに配置されるブロックは最初のページのみ報告する
+pub fn check_layout(document: &Document, diagnostics: &Diagnostics) {
+    let laid_out_document = pdf_writer::layout(document, false);
+    let mut reported: HashSet<(String, &str)> = HashSet::new();
+
+    for (page_index, page) in laid_out_document.pages.iter().enumerate() {
+        let page_size = page.page_size().unwrap_or(&laid_out_document.page_size);
+        for block in page.drawn_blocks() {
+            check_block(block, &GeoPoint::default(), page_size, page_index, diagnostics, &mut reported);
+        }
+    }
+}
+
+// NOTE: origin は親の frame の左上（ページの左上基準）
+fn check_block(
+    block: &Block,
+    origin: &GeoPoint,
+    page_size: &GeoSize,
+    page_index: usize,
+    diagnostics: &Diagnostics,
+    reported: &mut HashSet<(String, &'static str)>,
+) {
+    let pointer = block.source_pointer().unwrap_or_default().to_string();
+    let frame = block.frame();
+    let point = frame.and_then(|frame| frame.point.clone()).unwrap_or_default();
+    let size = frame.and_then(|frame| frame.size.clone()).unwrap_or_default();
+    let x = origin.x + point.x;
+    let y = origin.y + point.y;
+
+    let is_outside = x < -TOLERANCE
+        || y < -TOLERANCE
+        || x + size.width > page_size.width + TOLERANCE
+        || y + size.height > page_size.height + TOLERANCE;
+    if is_outside {
+        // NOTE: 親がページの外にある場合は子要素を報告しない
+        if reported.insert((pointer.clone(), WARNING_OUTSIDE_PAGE)) {
+            diagnostics.warning(
+                &pointer,
+                format!(
+                    "{} is outside the page frame on page {} (x: {:.1}, y: {:.1}, width: {:.1}, height: {:.1} mm; page: {:.1} x {:.1} mm)",
+                    block.type_name(),
+                    page_index + 1,
+                    x,
+                    y,
+                    size.width,
+                    size.height,
+                    page_size.width,
+                    page_size.height,
+                ),
+            );
+        }
+
+        return;
+    }
+
+    if let Block::Text(text) = block {
+        let is_clipped = text.get_wrapped_text().is_some_and(|wrapped_text| wrapped_text.is_truncated)
+            && matches!(text.get_text_wrap().overflow, TextOverflow::Clip);
+        if is_clipped && reported.insert((pointer.clone(), WARNING_CLIPPED_TEXT)) {
+            let preview: String = text.text.chars().take(20).collect();
+            diagnostics.warning(
+                &pointer,
+                format!("text is clipped by text_overflow: clip on page {} ({:?})", page_index + 1, preview),
+            );
+        }
+    }
+
+    let child_origin = GeoPoint { x, y };
+    for child in block.children() {
+        check_block(child, &child_origin, page_size, page_index, diagnostics, reported);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::block_document::asset_path::AssetPaths;
+use crate::block_document::data_uri;
+use crate::block_document::input_format::InputFormat;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// NOTE: 変更を確認する間隔（エディタの保存が終わるまで待つため、変更を検知した後も同じ間隔だけ待つ）
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// NOTE: 入力ファイルが参照するファイル（include されたコンポーネント、フォント、画像）
+// NOTE: 読み込めないファイルは無視する（エラーの表示は PDF の生成に任せる）
+pub fn dependencies(json_path: &str, format: Option<InputFormat>, asset_paths: &AssetPaths) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = vec![absolute(json_path)];
+    if let Some(json) = read(json_path, format) {
+        collect_includes(&json["document"]["include"], asset_paths, &mut paths);
+        collect_assets(&json, asset_paths, &mut paths);
+    }
+
+    let mut unique_paths: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if !unique_paths.contains(&path) {
+            unique_paths.push(path);
+        }
+    }
+    unique_paths
+}
+
+// NOTE: いずれかのファイルが変更（作成・削除を含む）されるまで待ち、変更されたファイルを返す
+pub fn wait_for_change(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let previous_times = modified_times(paths);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let changed: Vec<PathBuf> = paths
+            .iter()
+            .zip(modified_times(paths).iter().zip(previous_times.iter()))
+            .filter(|(_, (current, previous))| current != previous)
+            .map(|(path, _)| path.clone())
+            .collect();
+        if !changed.is_empty() {
+            thread::sleep(POLL_INTERVAL);
+            return changed;
+        }
+    }
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+fn read(json_path: &str, format: Option<InputFormat>) -> Option<Value> {
+    let source = fs::read_to_string(json_path).ok()?;
+    format.unwrap_or(InputFormat::from_path(json_path)).parse(&source).ok()
+}
+
+// NOTE: include されたファイル内の include はそのファイルからの相対パス（フォントや画像は入力ファイルからの相対パス）
+fn collect_includes(include_json: &Value, asset_paths: &AssetPaths, paths: &mut Vec<PathBuf>) {
+    let Some(includes) = include_json.as_array() else {
+        return;
+    };
+
+    for include in includes.iter().filter_map(|include| include.as_str()) {
+        let path = absolute(&asset_paths.resolve(include));
+        if paths.contains(&path) {
+            continue;
+        }
+        paths.push(path.clone());
+
+        let Some(json) = read(&path.to_string_lossy(), None) else {
+            continue;
+        };
+        let include_asset_paths = AssetPaths::new(path.parent().unwrap().to_path_buf());
+        collect_includes(&json["include"], &include_asset_paths, paths);
+        collect_assets(&json, asset_paths, paths);
+    }
+}
+
+// NOTE: font_path（スタイルやクラスを含む）と画像の path
+fn collect_assets(json: &Value, asset_paths: &AssetPaths, paths: &mut Vec<PathBuf>) {
+    match json {
+        Value::Object(object) => {
+            collect_asset(object, "font_path", asset_paths, paths);
+            if object.get("type").and_then(|object_type| object_type.as_str()) == Some("image") {
+                collect_asset(object, "path", asset_paths, paths);
+            }
+            object.values().for_each(|value| collect_assets(value, asset_paths, paths));
+        }
+        Value::Array(array) => array.iter().for_each(|value| collect_assets(value, asset_paths, paths)),
+        _ => {}
+    }
+}
+
+fn collect_asset(object: &Map<String, Value>, key: &str, asset_paths: &AssetPaths, paths: &mut Vec<PathBuf>) {
+    if let Some(path) = object.get(key).and_then(|path| path.as_str())
+        && !path.is_empty()
+        && !data_uri::is_data_uri(path)
+    {
+        paths.push(absolute(&asset_paths.resolve(path)));
+    }
+}
+
+fn absolute(path: &str) -> PathBuf {
+    std::path::absolute(Path::new(path)).unwrap_or(PathBuf::from(path))
+}
@@ -7,6 +7,7 @@ pub struct Wrapper {
     pub block: Block,
     pub frame: Option<GeoRect>,
     pub styles: Vec<Style>,
+    pub source_pointer: Option<String>, // NOTE: 入力 JSON 上の位置（JSON ポインタ）
 }
 
 impl Wrapper {
@@ -15,6 +16,7 @@ impl Wrapper {
             block,
             frame: None,
             styles: vec![],
+            source_pointer: None,
         }
     }
 
@@ -25,4 +27,8 @@ impl Wrapper {
     pub fn set_frame(&mut self, frame: GeoRect) {
         self.frame = Some(frame);
     }
+
+    pub fn set_source_pointer(&mut self, source_pointer: String) {
+        self.source_pointer = Some(source_pointer);
+    }
 }
@@ -1,52 +1,546 @@
-mod block_document;
+use rs_pdf::block_document;
+use block_document::asset_path::AssetPaths;
+use block_document::batch;
+use block_document::diagnostic::Diagnostics;
 use block_document::document_json;
-use block_document::pdf_writer;
-use clap::Parser;
+use block_document::document_schema;
+use block_document::document::Document;
+use block_document::input_format::{InputFormat, STDIN_PATH};
+use block_document::layout_dump;
+use block_document::output_format::OutputFormat;
+use block_document::pdf_writer::{self, LaidOutDocument};
+use block_document::png_writer;
+use block_document::server::{self, Server};
+use block_document::template;
+use block_document::validation;
+use block_document::watch;
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
 use std::fs::File;
-use std::path::Path;
-use std::process::exit;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command as ProcessCommand};
+use std::time::Duration;
+
+// NOTE: output に指定すると標準出力に書き込む
+const STDOUT_PATH: &str = "-";
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(short, long)]
-    input: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    // NOTE: "-" の場合は標準入力から読み込む
+    #[arg(short, long, required = true)]
+    input: Option<String>,
 
-    #[arg(short, long)]
-    output: String,
+    // NOTE: "-" の場合は標準出力に書き込む
+    #[arg(short, long, required = true)]
+    output: Option<String>,
 
     #[arg(short, long, default_value_t = false)]
     debug: bool,
 
     #[arg(short, long, default_value_t = false)]
     allow_override: bool,
+
+    #[command(flatten)]
+    input_args: InputArgs,
+
+    #[arg(long, conflicts_with = "batch")]
+    data: Option<String>,
+
+    // NOTE: CSV または JSON Lines のレコードごとに PDF を生成する
+    #[arg(long)]
+    batch: Option<String>,
+
+    // NOTE: 指定された場合は output をディレクトリとしてレコードごとに保存する（例: note-{{order_id}}.pdf）
+    #[arg(long, requires = "batch")]
+    filename: Option<String>,
+
+    // NOTE: レイアウトの結果（ブロックごとの frame や折り返した行）を JSON で保存する
+    #[arg(long, conflicts_with = "filename")]
+    dump_layout: Option<String>,
+
+    // NOTE: 未指定の場合は output の拡張子で判定する（--format は入力ファイルの形式）
+    #[arg(long, value_enum)]
+    output_format: Option<OutputFormat>,
+
+    // NOTE: 同じ入力から同じバイト列の PDF を出力する（キャッシュや重複排除用）
+    #[arg(long, default_value_t = false)]
+    reproducible: bool,
+
+    // NOTE: PNG で出力する場合の解像度
+    #[arg(long, default_value_t = png_writer::DEFAULT_DPI)]
+    dpi: f32,
+
+    // NOTE: 入力ファイルや参照しているファイルが変更されるたびに出力し直す（エラーがあっても終了しない）
+    #[arg(long, default_value_t = false)]
+    watch: bool,
+}
+
+// NOTE: input / output はサブコマンドがない場合のみ必須（clap で検証済み）
+impl Args {
+    fn input(&self) -> &str {
+        self.input.as_deref().unwrap()
+    }
+
+    fn output(&self) -> &str {
+        self.output.as_deref().unwrap()
+    }
+
+    fn output_format(&self, output: &str) -> OutputFormat {
+        self.output_format.unwrap_or_else(|| OutputFormat::from_path(output))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    // NOTE: CI などでテンプレートを検証する（スキーマ、フォントと画像の読み込み、レイアウト）
+    #[command(about = "Validate layout files without rendering PDFs")]
+    Validate(ValidateArgs),
+
+    // NOTE: 他の言語のサービスから HTTP で使用する（POST /render, POST /validate, GET /health）
+    #[command(about = "Run a local HTTP server that renders PDFs")]
+    Serve(ServeArgs),
+
+    // NOTE: エディタの補完に使用する JSON スキーマ（schema/schema.json はこの出力で更新する）
+    #[command(about = "Print the JSON schema for layout files")]
+    Schema,
+
+    // NOTE: serve が1リクエストごとに起動する子プロセス（直接は使用しない）
+    #[command(name = server::WORKER_COMMAND, hide = true)]
+    ServeWorker,
+}
+
+#[derive(clap::Args, Debug)]
+struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+
+    // NOTE: リクエストの本文の最大サイズ（バイト）
+    #[arg(long, default_value_t = server::DEFAULT_MAX_REQUEST_SIZE)]
+    max_request_size: usize,
+
+    // NOTE: 1リクエストの描画の制限時間（秒）
+    #[arg(long, default_value_t = server::DEFAULT_TIMEOUT_SECONDS)]
+    timeout: u64,
+
+    // NOTE: base_dir を指定しない場合はカレントディレクトリを基準にする
+    #[command(flatten)]
+    input_args: InputArgs,
+}
+
+#[derive(clap::Args, Debug)]
+struct ValidateArgs {
+    #[arg(required = true)]
+    inputs: Vec<String>,
+
+    // NOTE: テンプレートに差し込むデータ（プレースホルダーを含むテンプレートの検証用）
+    #[arg(long)]
+    data: Option<String>,
+
+    #[command(flatten)]
+    input_args: InputArgs,
+}
+
+// NOTE: 入力ファイルの読み込みに関するオプション（PDF の出力と validate で共通）
+#[derive(clap::Args, Debug)]
+struct InputArgs {
+    // NOTE: 未指定の場合は入力ファイルの拡張子で判定する
+    #[arg(long, value_enum)]
+    format: Option<InputFormat>,
+
+    // NOTE: 相対パスの font_path や画像の path の基準ディレクトリ（未指定の場合は入力ファイルのディレクトリ）
+    #[arg(long)]
+    base_dir: Option<String>,
+
+    // NOTE: 基準ディレクトリで見つからない場合に探すディレクトリ（複数指定可）
+    #[arg(long)]
+    asset_root: Vec<String>,
 }
 
 fn main() {
     let args = Args::parse();
 
-    if !args.allow_override && Path::new(args.output.as_str()).exists() {
-        eprintln!("The output path already exists!");
-        exit(1);
+    match args.command.as_ref() {
+        Some(Command::Validate(validate_args)) => {
+            validate(validate_args);
+            return;
+        }
+        Some(Command::Serve(serve_args)) => {
+            serve(serve_args);
+            return;
+        }
+        Some(Command::Schema) => {
+            println!("{}", serde_json::to_string_pretty(&document_schema::generate()).unwrap());
+            return;
+        }
+        Some(Command::ServeWorker) => {
+            server::run_worker();
+            return;
+        }
+        None => {}
     }
 
-    if !Path::new(args.input.as_str()).exists() {
+    if args.input() != STDIN_PATH && !Path::new(args.input()).exists() {
         eprintln!("The input path does not exist!");
         exit(1);
     }
 
-    let output_file = File::create(args.output.as_str()).map_err(|e| {
-        eprintln!("Could not create output file! {}", e);
-        e
-    });
-    let file = match output_file {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Fatal error: {}", e);
+    if args.data.as_ref().is_some_and(|data| !Path::new(data.as_str()).exists()) {
+        eprintln!("The data path does not exist!");
+        exit(1);
+    }
+
+    if args.batch.as_ref().is_some_and(|batch| !Path::new(batch.as_str()).exists()) {
+        eprintln!("The batch data path does not exist!");
+        exit(1);
+    }
+
+    if args.dpi.is_nan() || args.dpi <= 0.0 || args.dpi > png_writer::MAX_DPI {
+        eprintln!("The DPI must be greater than 0 and at most {}!", png_writer::MAX_DPI);
+        exit(1);
+    }
+
+    check_input_args(&args.input_args);
+    let asset_paths = asset_paths(args.input(), &args.input_args);
+
+    if args.watch {
+        watch_and_render(&args, &asset_paths);
+    }
+
+    if let (Some(batch), Some(filename)) = (args.batch.as_ref(), args.filename.as_ref()) {
+        if args.output() == STDOUT_PATH {
+            eprintln!("The output must be a directory when --filename is specified!");
             exit(1);
         }
+
+        save_each(&args, batch, filename, &asset_paths);
+        return;
+    }
+
+    // NOTE: すべてのレコードを1つの PDF に連結する
+    if let Some(batch) = args.batch.as_ref() {
+        let template = document_json::read(args.input(), args.input_args.format);
+        let documents = read_records(batch)
+            .iter()
+            .map(|record| document_json::parse_value(&template::bind(&template, record), &asset_paths))
+            .collect();
+        render(documents, args.output(), &args).unwrap_or_else(|e| exit_with_error(&e));
+        return;
+    }
+
+    let document = match args.data.as_ref() {
+        Some(data) => {
+            let template = document_json::read(args.input(), args.input_args.format);
+            let data = document_json::read(data.as_str(), None);
+            document_json::parse_value(&template::bind(&template, &data), &asset_paths)
+        }
+        None => document_json::parse(args.input(), args.input_args.format, &asset_paths),
     };
+    render(vec![document], args.output(), &args).unwrap_or_else(|e| exit_with_error(&e));
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1);
+}
+
+// NOTE: --dump-layout が指定されている場合はレイアウトの結果も保存する
+// NOTE: 出力できない場合はメッセージを返す（バッチ処理で他のレコードの出力を続けるため）
+fn render(documents: Vec<Document>, output: &str, args: &Args) -> Result<(), String> {
+    let laid_out_documents = documents
+        .iter()
+        .map(|document| pdf_writer::layout(document, args.reproducible))
+        .collect::<Vec<_>>();
+
+    if let Some(dump_layout) = args.dump_layout.as_ref() {
+        let asset_paths = asset_paths(args.input(), &args.input_args);
+        layout_dump::save(dump_layout, &laid_out_documents, &asset_paths, args.allow_override);
+    }
+
+    match args.output_format(output) {
+        OutputFormat::Pdf => {
+            let writer = create_output(output, args.allow_override)?;
+            pdf_writer::save_laid_out(laid_out_documents, writer, args.debug, args.reproducible).map_err(write_error)
+        }
+        OutputFormat::Png => save_png(&laid_out_documents, output, args),
+    }
+}
+
+// NOTE: ページごとに PNG で保存する（--debug の表示は PDF のみ）
+fn save_png(laid_out_documents: &[LaidOutDocument], output: &str, args: &Args) -> Result<(), String> {
+    png_writer::check_pixels(laid_out_documents, args.dpi)?;
+
+    let images = png_writer::render(laid_out_documents, args.dpi);
+    if images.len() > 1 && output == STDOUT_PATH {
+        return Err("The output must be a file when rendering multiple pages to PNG!".to_string());
+    }
+
+    for (image, path) in images.iter().zip(png_writer::page_paths(output, images.len())) {
+        let writer = BufWriter::new(create_output(&path, args.allow_override)?);
+        png_writer::write(image, writer).map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+// NOTE: 出力先のパイプが閉じられた場合（`-o - | head` など）はエラーを表示せずに終了する
+fn write_error(e: io::Error) -> String {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        exit(0);
+    }
+
+    format!("Could not write output! {}", e)
+}
+
+// NOTE: 出力は --watch を除いた同じ引数で子プロセスとして実行する（エラーで exit しても監視を続けるため）
+// NOTE: 既存のファイルは通常と同じく上書きしない。自身が出力したファイルは2回目以降に上書きする
+fn watch_and_render(args: &Args, asset_paths: &AssetPaths) -> ! {
+    if args.input() == STDIN_PATH || args.output() == STDOUT_PATH {
+        eprintln!("The input and output must be files when --watch is specified!");
+        exit(1);
+    }
+
+    let executable = env::current_exe().unwrap_or_else(|e| {
+        eprintln!("Could not find the executable! {}", e);
+        exit(1);
+    });
+    let mut render_args: Vec<_> = env::args_os().skip(1).filter(|arg| arg != "--watch").collect();
+    let mut allow_override = args.allow_override;
+
+    loop {
+        match ProcessCommand::new(&executable).args(&render_args).status() {
+            Ok(status) if status.success() => {
+                eprintln!("Rendered {}", args.output());
+                if !allow_override {
+                    render_args.push("--allow-override".into());
+                    allow_override = true;
+                }
+            }
+            Ok(_) => eprintln!("Failed to render {}", args.input()),
+            Err(e) => {
+                eprintln!("Could not run the renderer! {}", e);
+                exit(1);
+            }
+        }
+
+        let mut paths = watch::dependencies(args.input(), args.input_args.format, asset_paths);
+        paths.extend(
+            [args.data.as_ref(), args.batch.as_ref()]
+                .into_iter()
+                .flatten()
+                .filter_map(|path| std::path::absolute(path).ok()),
+        );
+        eprintln!("Watching {} file(s) for changes... (Ctrl+C to stop)", paths.len());
+
+        for path in watch::wait_for_change(&paths).iter() {
+            eprintln!("Changed: {}", path.display());
+        }
+    }
+}
+
+fn check_input_args(input_args: &InputArgs) {
+    if input_args.base_dir.as_ref().is_some_and(|base_dir| !Path::new(base_dir.as_str()).is_dir()) {
+        eprintln!("The base directory does not exist!");
+        exit(1);
+    }
+
+    if let Some(asset_root) = input_args
+        .asset_root
+        .iter()
+        .find(|asset_root| !Path::new(asset_root.as_str()).is_dir())
+    {
+        eprintln!("The asset root does not exist! {}", asset_root);
+        exit(1);
+    }
+}
+
+fn asset_paths(input: &str, input_args: &InputArgs) -> AssetPaths {
+    let base_dir = match input_args.base_dir.as_ref() {
+        Some(base_dir) => PathBuf::from(base_dir),
+        None if input == STDIN_PATH => PathBuf::new(),
+        None => Path::new(input).parent().map(Path::to_path_buf).unwrap_or_default(),
+    };
+
+    let mut asset_paths = AssetPaths::new(base_dir);
+    input_args
+        .asset_root
+        .iter()
+        .for_each(|asset_root| asset_paths.add_asset_root(PathBuf::from(asset_root)));
+
+    asset_paths
+}
+
+// NOTE: PDF を出力せずにすべての入力を検証する（エラーがあれば終了コード 1）
+fn validate(args: &ValidateArgs) {
+    check_input_args(&args.input_args);
+
+    if args.data.as_ref().is_some_and(|data| !Path::new(data.as_str()).exists()) {
+        eprintln!("The data path does not exist!");
+        exit(1);
+    }
+    let data = args.data.as_ref().map(|data| document_json::read(data.as_str(), None));
+
+    let mut has_errors = false;
+    for input in args.inputs.iter() {
+        if input != STDIN_PATH && !Path::new(input.as_str()).exists() {
+            eprintln!("The input path does not exist! {}", input);
+            has_errors = true;
+            continue;
+        }
+
+        let asset_paths = asset_paths(input, &args.input_args);
+        let (json, source_map) = document_json::read_with_source_map(input, args.input_args.format);
+
+        // NOTE: データを差し込んだ場合は入力ファイルと位置が対応しないため JSON ポインタのみ表示する
+        let (json, source_map) = match data.as_ref() {
+            Some(data) => (template::bind(&json, data), None),
+            None => (json, source_map),
+        };
+
+        let diagnostics = Diagnostics::new(source_map.as_ref());
+        if let Some(document) = document_json::parse_with_diagnostics(&json, &asset_paths, &diagnostics)
+            && !diagnostics.has_errors()
+        {
+            validation::check_layout(&document, &diagnostics);
+        }
+
+        diagnostics.report();
+        if diagnostics.has_errors() {
+            println!("{}: failed", input);
+            has_errors = true;
+        } else {
+            println!("{}: ok", input);
+        }
+    }
+
+    if has_errors {
+        exit(1);
+    }
+}
+
+fn serve(args: &ServeArgs) {
+    check_input_args(&args.input_args);
+
+    if args.timeout == 0 {
+        eprintln!("The timeout must be greater than 0!");
+        exit(1);
+    }
+
+    let mut server = Server::new(
+        format!("{}:{}", args.host, args.port),
+        asset_paths(STDIN_PATH, &args.input_args),
+    );
+    if let Some(format) = args.input_args.format {
+        server.set_format(format);
+    }
+    server.set_max_request_size(args.max_request_size);
+    server.set_timeout(Duration::from_secs(args.timeout));
+    server.run();
+}
+
+// NOTE: 読み込めないレコードがある場合は何も出力せずに終了する（すべてのレコードを1つの PDF に連結する場合）
+fn read_records(batch: &str) -> Vec<Value> {
+    let records = batch::read_records(batch);
+    for (i, record) in records.iter().enumerate() {
+        if let Err(e) = record {
+            eprintln!("Record {}: {}", i + 1, e);
+        }
+    }
+
+    records.into_iter().collect::<Result<Vec<_>, _>>().unwrap_or_else(|_| exit(1))
+}
+
+// NOTE: レコードごとに output ディレクトリへ保存する
+// NOTE: 出力ファイル名の重複や既存ファイルは書き込む前に確認し、読み込めないレコードや不正なファイル名はレコードごとに報告する
+fn save_each(args: &Args, batch: &str, filename: &str, asset_paths: &AssetPaths) {
+    if let Err(e) = fs::create_dir_all(args.output()) {
+        eprintln!("Could not create output directory! {}", e);
+        exit(1);
+    }
+
+    let outputs: Vec<Result<(PathBuf, Value), String>> = batch::read_records(batch)
+        .into_iter()
+        .map(|record| {
+            let record = record?;
+            let output = Path::new(args.output()).join(batch::file_name(filename, &record)?);
+            Ok((output, record))
+        })
+        .collect();
+
+    let mut record_numbers: HashMap<&PathBuf, usize> = HashMap::new();
+    let mut has_conflicts = false;
+    for (i, output) in outputs.iter().enumerate() {
+        let Ok((output, _)) = output else {
+            continue;
+        };
+
+        if let Some(record_number) = record_numbers.insert(output, i + 1) {
+            eprintln!("Record {}: The output path is the same as record {}! {}", i + 1, record_number, output.display());
+            has_conflicts = true;
+        } else if !args.allow_override && output.exists() {
+            eprintln!("Record {}: The output path already exists! {}", i + 1, output.display());
+            has_conflicts = true;
+        }
+    }
+    if has_conflicts {
+        exit(1);
+    }
+
+    let template = document_json::read(args.input(), args.input_args.format);
+    let mut failed_count = 0;
+    for (i, output) in outputs.iter().enumerate() {
+        let (output, record) = match output {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Record {}: {}", i + 1, e);
+                failed_count += 1;
+                continue;
+            }
+        };
+
+        let diagnostics = Diagnostics::new(None);
+        let document = document_json::parse_with_diagnostics(&template::bind(&template, record), asset_paths, &diagnostics);
+        diagnostics.report_with_prefix(&format!("Record {}", i + 1));
+        let Some(document) = document.filter(|_| !diagnostics.has_errors()) else {
+            failed_count += 1;
+            continue;
+        };
+
+        if let Err(e) = render(vec![document], output.to_str().unwrap(), args) {
+            eprintln!("Record {}: {}", i + 1, e);
+            failed_count += 1;
+        }
+    }
+
+    if failed_count > 0 {
+        eprintln!("{} of {} record(s) failed", failed_count, outputs.len());
+        exit(1);
+    }
+}
+
+fn create_output(output: &str, allow_override: bool) -> Result<Box<dyn Write>, String> {
+    if output == STDOUT_PATH {
+        return Ok(Box::new(io::stdout().lock()));
+    }
+
+    Ok(Box::new(create_output_file(output, allow_override)?))
+}
+
+fn create_output_file(output: &str, allow_override: bool) -> Result<File, String> {
+    if !allow_override && Path::new(output).exists() {
+        return Err(format!("The output path already exists! {}", output));
+    }
 
-    let document = document_json::parse(args.input.as_str());
-    pdf_writer::save(document, file, args.debug);
+    File::create(output).map_err(|e| format!("Could not create output file! {}", e))
 }
@@ -0,0 +1,161 @@
+use rs_pdf::block_document::asset_path::AssetPaths;
+use rs_pdf::block_document::block_container::BlockContainer;
+use rs_pdf::block_document::diagnostic::Diagnostics;
+use rs_pdf::block_document::direction::Direction;
+use rs_pdf::block_document::document::Document;
+use rs_pdf::block_document::document_json;
+use rs_pdf::block_document::flexible_container::FlexibleContainer;
+use rs_pdf::block_document::geometry::{GeoRect, GeoSize};
+use rs_pdf::block_document::image::Image;
+use rs_pdf::block_document::page_number::PageNumber;
+use rs_pdf::block_document::rectangle::Rectangle;
+use rs_pdf::block_document::style::{Alignment, HorizontalAlignment, RgbColor, Space};
+use rs_pdf::block_document::text::Text;
+use rs_pdf::block_document::wrapper::Wrapper;
+use serde_json::json;
+use std::path::Path;
+
+// NOTE: builder で組み立てた Document が、同じレイアウトの JSON を document_json で解析した結果と一致することを確認する
+#[test]
+fn builder_matches_document_json() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let asset = |path: &str| manifest_dir.join(path).to_string_lossy().to_string();
+    let gray = RgbColor { r: 100, g: 100, b: 100 };
+
+    let built = Document::builder("rs-pdf", asset("assets/fonts/NotoSansCJKjp-Thin.ttf"))
+        .margin(Space { top: 10.0, right: 10.0, bottom: 10.0, left: 10.0 })
+        .page_number(PageNumber::builder(10.0).text_fill_color(gray.clone()))
+        .dynamic_page(|page| {
+            page.common(
+                BlockContainer::builder()
+                    .point(10.0, 10.0)
+                    .direction(Direction::Vertical)
+                    .object(Image::builder(asset("assets/images/delivery_note_rgb.png")))
+                    .object(
+                        Wrapper::builder(Text::builder("〒000-0000", 10.0))
+                            .space(Space { top: 5.0, right: 0.0, bottom: 0.0, left: 0.0 }),
+                    ),
+            )
+            .content(
+                FlexibleContainer::builder()
+                    .size(190.0, 8.0)
+                    .direction(Direction::Horizontal)
+                    .item(Text::builder("商品", 10.0), Some(70.0))
+                    .item(
+                        Text::builder("1,000 円", 10.0).alignment(Alignment {
+                            horizontal: Some(HorizontalAlignment::Right),
+                            vertical: None,
+                        }),
+                        None,
+                    ),
+            )
+        })
+        .static_page(|page| {
+            page.page_size(GeoSize { width: 100.0, height: 50.0 })
+                .object(
+                    Rectangle::builder()
+                        .frame(GeoRect::new(80.0, 30.0, 10.0, 10.0))
+                        .background_color(RgbColor { r: 240, g: 240, b: 240 })
+                        .border_color(gray.clone())
+                        .border_width(0.5),
+                )
+                .object(Text::builder("以上", 12.0).point(15.0, 15.0).text_fill_color(gray.clone()))
+        })
+        .build();
+
+    let json = json!({
+        "document": {
+            "title": "rs-pdf",
+            "width": 210.0,
+            "height": 297.0,
+            "margin": { "top": 10.0, "right": 10.0, "bottom": 10.0, "left": 10.0 },
+            "font_path": "assets/fonts/NotoSansCJKjp-Thin.ttf",
+            "page_number": {
+                "font_size": 10.0,
+                "style": { "text_fill_color": { "red": 100, "green": 100, "blue": 100 } }
+            },
+            "pages": [
+                {
+                    "type": "dynamic",
+                    "common": {
+                        "objects": [
+                            {
+                                "type": "objects",
+                                "direction": "vertical",
+                                "frame": { "point": { "x": 10.0, "y": 10.0 }, "size": null },
+                                "objects": [
+                                    { "type": "image", "path": "assets/images/delivery_note_rgb.png" },
+                                    {
+                                        "type": "object",
+                                        "object": { "type": "text", "text": "〒000-0000", "font_size": 10.0 },
+                                        "style": { "space": { "top": 5.0, "right": 0.0, "bottom": 0.0, "left": 0.0 } }
+                                    }
+                                ]
+                            }
+                        ]
+                    },
+                    "content": {
+                        "objects": [
+                            {
+                                "type": "flexible",
+                                "direction": "horizontal",
+                                "frame": { "point": null, "size": { "width": 190.0, "height": 8.0 } },
+                                "objects": [
+                                    {
+                                        "type": "flexible_item",
+                                        "object": { "type": "text", "text": "商品", "font_size": 10.0 },
+                                        "basis": 70.0
+                                    },
+                                    {
+                                        "type": "flexible_item",
+                                        "object": {
+                                            "type": "text",
+                                            "text": "1,000 円",
+                                            "font_size": 10.0,
+                                            "style": { "alignment": { "horizontal": "right" } }
+                                        }
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                },
+                {
+                    "type": "static",
+                    "page_size": { "width": 100.0, "height": 50.0 },
+                    "objects": [
+                        {
+                            "type": "rectangle",
+                            "frame": {
+                                "point": { "x": 10.0, "y": 10.0 },
+                                "size": { "width": 80.0, "height": 30.0 }
+                            },
+                            "style": {
+                                "background_color": { "red": 240, "green": 240, "blue": 240 },
+                                "border_color": { "red": 100, "green": 100, "blue": 100 },
+                                "border_width": { "width": 0.5 }
+                            }
+                        },
+                        {
+                            "type": "text",
+                            "text": "以上",
+                            "font_size": 12.0,
+                            "frame": { "point": { "x": 15.0, "y": 15.0 }, "size": null },
+                            "style": { "text_fill_color": { "red": 100, "green": 100, "blue": 100 } }
+                        }
+                    ]
+                }
+            ]
+        }
+    });
+
+    let asset_paths = AssetPaths::new(manifest_dir.to_path_buf());
+    let diagnostics = Diagnostics::new(None);
+    let parsed = document_json::parse_with_diagnostics(&json, &asset_paths, &diagnostics);
+    assert!(!diagnostics.has_errors(), "{:?}", diagnostics.entries());
+
+    assert_eq!(
+        serde_json::to_value(&built).unwrap(),
+        serde_json::to_value(parsed.unwrap()).unwrap()
+    );
+}
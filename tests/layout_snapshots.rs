@@ -0,0 +1,207 @@
+use lopdf::content::Content;
+use lopdf::{Document as LoDocument, Object};
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// NOTE: tests/fixtures の入力ごとにレイアウト（--dump-layout）と PDF の描画命令をスナップショットと比較する
+// NOTE: スナップショットの更新: UPDATE_SNAPSHOTS=1 cargo test --test layout_snapshots
+const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+// NOTE: 許容誤差（mm）。PDF の描画命令の数値は pt なので換算して比較する
+const TOLERANCE_MM: f64 = 0.01;
+const TOLERANCE_PT: f64 = TOLERANCE_MM * 72.0 / 25.4;
+
+// NOTE: 差分が多い場合に表示する最大件数
+const MAX_DIFFERENCES: usize = 20;
+
+#[test]
+fn layout_snapshots() {
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let snapshots_dir = fixtures_dir.join("snapshots");
+    let is_update = env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| !value.is_empty() && value != "0");
+
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(&fixtures_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "No fixtures in {}", fixtures_dir.display());
+
+    let mut failures: Vec<String> = Vec::new();
+    for fixture in fixtures.iter() {
+        let name = fixture.file_stem().unwrap().to_str().unwrap();
+        let (layout, content) = render(fixture);
+
+        let layout_path = snapshots_dir.join(format!("{}.layout.json", name));
+        let content_path = snapshots_dir.join(format!("{}.content.txt", name));
+
+        if is_update {
+            fs::create_dir_all(&snapshots_dir).unwrap();
+            fs::write(&layout_path, serde_json::to_string_pretty(&layout).unwrap() + "\n").unwrap();
+            fs::write(&content_path, content.join("\n") + "\n").unwrap();
+            continue;
+        }
+
+        let Ok(expected_layout) = fs::read_to_string(&layout_path) else {
+            failures.push(format!("{}: missing snapshot {} (run with {}=1)", name, layout_path.display(), UPDATE_SNAPSHOTS));
+            continue;
+        };
+        let mut differences: Vec<String> = Vec::new();
+        compare_json(&serde_json::from_str(&expected_layout).unwrap(), &layout, "", &mut differences);
+
+        // NOTE: 描画命令のスナップショットは任意（削除すればレイアウトのみ比較する）
+        if let Ok(expected_content) = fs::read_to_string(&content_path) {
+            compare_content(&expected_content.lines().collect::<Vec<_>>(), &content, &mut differences);
+        }
+
+        if !differences.is_empty() {
+            let count = differences.len();
+            differences.truncate(MAX_DIFFERENCES);
+            failures.push(format!("{}: {} difference(s)\n  {}", name, count, differences.join("\n  ")));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Snapshots do not match (run with {}=1 to update):\n{}",
+        UPDATE_SNAPSHOTS,
+        failures.join("\n")
+    );
+}
+
+// NOTE: CLI で PDF とレイアウトの JSON を出力する
+fn render(fixture: &Path) -> (Value, Vec<String>) {
+    let name = fixture.file_stem().unwrap().to_str().unwrap();
+    let output_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("layout_snapshots");
+    fs::create_dir_all(&output_dir).unwrap();
+    let pdf_path = output_dir.join(format!("{}.pdf", name));
+    let layout_path = output_dir.join(format!("{}.layout.json", name));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rs-pdf"))
+        .arg("-i")
+        .arg(fixture)
+        .arg("-o")
+        .arg(&pdf_path)
+        .arg("--dump-layout")
+        .arg(&layout_path)
+        .arg("--allow-override")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}: rs-pdf failed\n{}",
+        name,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let layout = serde_json::from_str(&fs::read_to_string(&layout_path).unwrap()).unwrap();
+    (layout, normalized_content(&pdf_path))
+}
+
+// NOTE: ページごとの描画命令を1行1命令のテキストにする（数値は小数点以下3桁）
+fn normalized_content(pdf_path: &Path) -> Vec<String> {
+    let pdf = LoDocument::load(pdf_path).unwrap();
+    let mut lines: Vec<String> = Vec::new();
+
+    for (page_number, page_id) in pdf.get_pages() {
+        lines.push(format!("% page {}", page_number));
+        let content = Content::decode(&pdf.get_page_content(page_id).unwrap()).unwrap();
+        for operation in content.operations.iter() {
+            let mut tokens: Vec<String> = operation.operands.iter().map(format_object).collect();
+            tokens.push(operation.operator.clone());
+            lines.push(tokens.join(" "));
+        }
+    }
+
+    lines
+}
+
+fn format_object(object: &Object) -> String {
+    match object {
+        Object::Null => "null".to_string(),
+        Object::Boolean(boolean) => boolean.to_string(),
+        Object::Integer(integer) => integer.to_string(),
+        Object::Real(real) => format!("{:.3}", real),
+        Object::Name(name) => format!("/{}", String::from_utf8_lossy(name)),
+        Object::String(bytes, _) => format!(
+            "<{}>",
+            bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+        ),
+        Object::Array(array) => format!("[{}]", array.iter().map(format_object).collect::<Vec<_>>().join(" ")),
+        _ => format!("{:?}", object),
+    }
+}
+
+fn compare_json(expected: &Value, actual: &Value, pointer: &str, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Number(expected_number), Value::Number(actual_number)) => {
+            let (expected_value, actual_value) = (expected_number.as_f64().unwrap(), actual_number.as_f64().unwrap());
+            if (expected_value - actual_value).abs() > TOLERANCE_MM {
+                differences.push(format!("{}: expected {}, got {}", pointer, expected_value, actual_value));
+            }
+        }
+        (Value::Array(expected_array), Value::Array(actual_array)) => {
+            if expected_array.len() != actual_array.len() {
+                differences.push(format!(
+                    "{}: expected {} item(s), got {}",
+                    pointer,
+                    expected_array.len(),
+                    actual_array.len()
+                ));
+            }
+            for (i, (expected_item, actual_item)) in expected_array.iter().zip(actual_array.iter()).enumerate() {
+                compare_json(expected_item, actual_item, &format!("{}/{}", pointer, i), differences);
+            }
+        }
+        (Value::Object(expected_object), Value::Object(actual_object)) => {
+            for (key, expected_item) in expected_object.iter() {
+                match actual_object.get(key) {
+                    Some(actual_item) => compare_json(expected_item, actual_item, &format!("{}/{}", pointer, key), differences),
+                    None => differences.push(format!("{}/{}: missing", pointer, key)),
+                }
+            }
+            for key in actual_object.keys().filter(|key| !expected_object.contains_key(*key)) {
+                differences.push(format!("{}/{}: unexpected", pointer, key));
+            }
+        }
+        _ => {
+            if expected != actual {
+                differences.push(format!("{}: expected {}, got {}", pointer, expected, actual));
+            }
+        }
+    }
+}
+
+fn compare_content(expected: &[&str], actual: &[String], differences: &mut Vec<String>) {
+    if expected.len() != actual.len() {
+        differences.push(format!(
+            "content: expected {} operation(s), got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    for (i, (expected_line, actual_line)) in expected.iter().zip(actual.iter()).enumerate() {
+        let expected_tokens: Vec<&str> = expected_line.split(' ').collect();
+        let actual_tokens: Vec<&str> = actual_line.split(' ').collect();
+        let is_same = expected_tokens.len() == actual_tokens.len()
+            && expected_tokens.iter().zip(actual_tokens.iter()).all(|(expected_token, actual_token)| {
+                match (expected_token.parse::<f64>(), actual_token.parse::<f64>()) {
+                    (Ok(expected_value), Ok(actual_value)) => (expected_value - actual_value).abs() <= TOLERANCE_PT,
+                    _ => expected_token == actual_token,
+                }
+            });
+        if !is_same {
+            differences.push(format!(
+                "content line {}: expected `{}`, got `{}`",
+                i + 1,
+                expected_line,
+                actual_line
+            ));
+        }
+    }
+}
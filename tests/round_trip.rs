@@ -0,0 +1,57 @@
+use rs_pdf::block_document::asset_path::AssetPaths;
+use rs_pdf::block_document::diagnostic::Diagnostics;
+use rs_pdf::block_document::document::Document;
+use rs_pdf::block_document::document_json;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// NOTE: demo と tests/fixtures の入力を解析 → シリアライズ → 再解析し、同じ内容になることを確認する
+#[test]
+fn round_trip() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let inputs: Vec<PathBuf> = ["demo", "tests/fixtures"]
+        .iter()
+        .flat_map(|dir| fs::read_dir(manifest_dir.join(dir)).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+    assert!(!inputs.is_empty());
+
+    for input in inputs.iter() {
+        let asset_paths = AssetPaths::new(input.parent().unwrap().to_path_buf());
+        let json = document_json::read(input.to_str().unwrap(), None);
+        let document = parse(&json, &asset_paths, input);
+        let serialized = serde_json::to_value(&document).unwrap();
+
+        // NOTE: 出力はスキーマに沿った形式なので、入力と同じ解析（スキーマ検証を含む）で読み込める
+        let reparsed = parse(&serialized, &asset_paths, input);
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serialized,
+            "{}: re-parsed document differs",
+            input.display()
+        );
+
+        let deserialized = serde_json::from_value::<Document>(serialized.clone()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&deserialized).unwrap(),
+            serialized,
+            "{}: deserialized document differs",
+            input.display()
+        );
+    }
+}
+
+fn parse(json: &Value, asset_paths: &AssetPaths, input: &Path) -> Document {
+    let diagnostics = Diagnostics::new(None);
+    let document = document_json::parse_with_diagnostics(json, asset_paths, &diagnostics);
+    assert!(
+        !diagnostics.has_errors(),
+        "{}: {:?}",
+        input.display(),
+        diagnostics.entries()
+    );
+
+    document.unwrap()
+}
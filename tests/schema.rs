@@ -0,0 +1,34 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// NOTE: schema/schema.json が `rs-pdf schema` の出力（Rust の型から生成したスキーマ）と一致することを確認する
+// NOTE: 更新: UPDATE_SNAPSHOTS=1 cargo test --test schema
+const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+#[test]
+fn schema_is_up_to_date() {
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schema/schema.json");
+    let is_update = env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| !value.is_empty() && value != "0");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rs-pdf")).arg("schema").output().unwrap();
+    assert!(
+        output.status.success(),
+        "rs-pdf schema failed\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let schema = String::from_utf8(output.stdout).unwrap();
+
+    if is_update {
+        fs::write(&schema_path, &schema).unwrap();
+        return;
+    }
+
+    assert!(
+        fs::read_to_string(&schema_path).unwrap() == schema,
+        "{} is out of date (run `rs-pdf schema > schema/schema.json` or {}=1 cargo test --test schema)",
+        schema_path.display(),
+        UPDATE_SNAPSHOTS
+    );
+}